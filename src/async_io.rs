@@ -0,0 +1,276 @@
+//! Reading and writing CoNLL-U asynchronously with tokio. Requires the `tokio`
+//! feature.
+//!
+//! ```rust
+//! use rs_conllu::async_io::{AsyncDoc, AsyncWriter};
+//! use tokio_stream::StreamExt;
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let input = "1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_
+//!
+//! 1\tWorld\tworld\tNOUN\t_\t_\t0\troot\t_\t_
+//! ";
+//!
+//! let mut doc = AsyncDoc::new(input.as_bytes());
+//! let mut writer = AsyncWriter::new(vec![]);
+//!
+//! while let Some(sentence) = doc.next().await {
+//!     writer.write_sentence(&sentence.unwrap()).await.unwrap();
+//! }
+//!
+//! assert_eq!(writer.into_inner(), format!("{input}\n").into_bytes());
+//! # })
+//! ```
+
+use std::{
+    io,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use tokio::io::{AsyncBufRead, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    parsers::{DeclaredColumns, DocError, DocState, ParserOptions, Warning},
+    Sentence,
+};
+
+/// The asynchronous counterpart of [Doc](crate::parsers::Doc): a [Stream] of the
+/// sentences read from an [AsyncBufRead].
+///
+/// Sentences are split, decoded and parsed exactly as by [Doc](crate::parsers::Doc),
+/// including its handling of byte order marks, CRLF line endings, blank lines
+/// and invalid UTF-8, and it is configured the same way.
+pub struct AsyncDoc<R> {
+    reader: R,
+    state: DocState,
+    line_buffer: Vec<u8>,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncDoc<R> {
+    pub fn new(reader: R) -> Self {
+        AsyncDoc {
+            reader,
+            state: DocState::default(),
+            line_buffer: Vec::new(),
+        }
+    }
+
+    /// Enable or disable lossy decoding of invalid UTF-8, see
+    /// [Doc::lossy](crate::parsers::Doc::lossy).
+    pub fn lossy(mut self, lossy: bool) -> Self {
+        self.state.lossy = lossy;
+        self
+    }
+
+    /// Set the options for parsing the sentences of the document, see
+    /// [Doc::options](crate::parsers::Doc::options).
+    pub fn options(mut self, options: ParserOptions) -> Self {
+        self.state.options = options;
+        self
+    }
+
+    /// Enable or disable recording [Warning]s, see
+    /// [Doc::report_warnings](crate::parsers::Doc::report_warnings).
+    pub fn report_warnings(mut self, report: bool) -> Self {
+        self.state.report_warnings = report;
+        self
+    }
+
+    /// The warnings recorded so far.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.state.warnings
+    }
+
+    /// Remove and return the warnings recorded so far.
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.state.warnings)
+    }
+
+    /// The 1-based line number of the first line of the most recently read sentence.
+    pub fn sentence_line(&self) -> usize {
+        self.state.sentence_line()
+    }
+
+    /// The byte offset of the most recently read sentence within the input.
+    pub fn sentence_offset(&self) -> usize {
+        self.state.sentence_offset()
+    }
+
+    /// Read lines until the end of the next sentence, like `read_until` but
+    /// resumable when the reader is not ready.
+    fn poll_sentence(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<String, DocError>>> {
+        while !self.state.is_finished() {
+            let available = match ready!(Pin::new(&mut self.reader).poll_fill_buf(cx)) {
+                Ok(available) => available,
+                Err(e) => return Poll::Ready(Some(Err(self.state.fail(e)))),
+            };
+
+            if available.is_empty() {
+                // the last line may not end with a newline
+                if !self.line_buffer.is_empty() {
+                    let line = std::mem::take(&mut self.line_buffer);
+                    if let Some(sentence) = self.state.push_line(&line) {
+                        return Poll::Ready(Some(sentence));
+                    }
+                }
+                return Poll::Ready(self.state.finish());
+            }
+
+            let (len, complete) = match available.iter().position(|&b| b == b'\n') {
+                Some(end) => (end + 1, true),
+                None => (available.len(), false),
+            };
+            self.line_buffer.extend_from_slice(&available[..len]);
+            Pin::new(&mut self.reader).consume(len);
+
+            if complete {
+                let sentence = self.state.push_line(&self.line_buffer);
+                self.line_buffer.clear();
+                if sentence.is_some() {
+                    return Poll::Ready(sentence);
+                }
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> Stream for AsyncDoc<R> {
+    type Item = Result<Sentence, DocError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let sentence = ready!(this.poll_sentence(cx));
+
+        Poll::Ready(sentence.map(|buffer| this.state.parse(&buffer?)))
+    }
+}
+
+/// The asynchronous counterpart of [Writer](crate::Writer).
+pub struct AsyncWriter<W> {
+    writer: W,
+    columns: DeclaredColumns,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWriter<W> {
+    pub fn new(writer: W) -> Self {
+        AsyncWriter {
+            writer,
+            columns: DeclaredColumns::default(),
+        }
+    }
+
+    /// Write a single sentence, including its comment lines and
+    /// the terminating blank line.
+    pub async fn write_sentence(&mut self, sentence: &Sentence) -> io::Result<()> {
+        self.columns.update(&sentence.meta);
+
+        let text = format!("{}\n", sentence.display_with(&self.columns.columns()));
+        self.writer.write_all(text.as_bytes()).await
+    }
+
+    /// Flush the underlying writer.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush().await
+    }
+
+    /// Consume the `AsyncWriter` and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio_stream::StreamExt;
+
+    use super::*;
+    use crate::parsers::Doc;
+
+    #[tokio::test]
+    async fn test_async_doc_matches_doc() {
+        let input = "# sent_id = 1
+1\tThey\tthey\tPRON\t_\t_\t2\tnsubj\t_\t_
+2\tbuy\tbuy\tVERB\t_\t_\t0\troot\t_\t_
+
+
+# global.columns = ID FORM MWE
+# sent_id = 2
+1\tkicked\t1:VID
+
+1\tbroken
+";
+        let sequential: Vec<_> = Doc::new(input.as_bytes())
+            .map(|s| s.map_err(|e| e.to_string()))
+            .collect();
+        let asynchronous: Vec<_> = AsyncDoc::new(input.as_bytes())
+            .map(|s| s.map_err(|e| e.to_string()))
+            .collect()
+            .await;
+
+        assert_eq!(asynchronous.len(), 3);
+        assert_eq!(asynchronous, sequential);
+        assert_eq!(
+            asynchronous[2].as_ref().unwrap_err(),
+            "Parse error in line 10: Missing column: MWE"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_doc_irregular_input() {
+        let input = b"\xef\xbb\xbf# sent_id = 1\r\n1\tSue\t_\t_\t_\t_\t_\t_\t_\t_\r\n\r\n\r\n\
+1\tbad\xff\t_\t_\t_\t_\t_\t_\t_\t_\n\n\
+1 likes likes VERB\n\n\
+1\tcoffee\t_\t_\t_\t_\t_\t_\t_\t_";
+
+        for lossy in [false, true] {
+            let mut doc = Doc::new(&input[..])
+                .lossy(lossy)
+                .options(ParserOptions::lenient())
+                .report_warnings(true);
+            let mut sequential = vec![];
+            while let Some(sentence) = doc.next() {
+                let sentence = sentence.map_err(|e| e.to_string());
+                sequential.push((sentence, doc.sentence_line(), doc.sentence_offset()));
+            }
+
+            let mut async_doc = AsyncDoc::new(&input[..])
+                .lossy(lossy)
+                .options(ParserOptions::lenient())
+                .report_warnings(true);
+            let mut asynchronous = vec![];
+            while let Some(sentence) = async_doc.next().await {
+                let sentence = sentence.map_err(|e| e.to_string());
+                asynchronous.push((
+                    sentence,
+                    async_doc.sentence_line(),
+                    async_doc.sentence_offset(),
+                ));
+            }
+
+            assert_eq!(asynchronous.len(), 4);
+            assert_eq!(asynchronous[1].0.is_err(), !lossy);
+            assert_eq!(asynchronous, sequential);
+            assert_eq!(async_doc.warnings(), doc.warnings());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_writer_conllu_plus() {
+        let input = "# global.columns = ID FORM MWE
+1\tkicked\t1:VID
+
+1\tit\t*
+
+";
+        let mut writer = AsyncWriter::new(vec![]);
+        let mut doc = AsyncDoc::new(input.as_bytes());
+        while let Some(sentence) = doc.next().await {
+            writer.write_sentence(&sentence.unwrap()).await.unwrap();
+        }
+
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), input);
+    }
+}
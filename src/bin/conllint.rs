@@ -1,28 +1,327 @@
-use std::fs::File;
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    process::ExitCode,
+};
 
 use clap::Parser;
-use rs_conllu::{cli, parse_file};
+use rs_conllu::{
+    cli::{self, OutputFormat},
+    parsers::Doc,
+    validate::{Finding, Validator},
+};
 
-fn main() {
-    let cli = cli::LintCli::parse();
-    let walker = walkdir::WalkDir::new(cli.path).into_iter();
+/// A finding together with the file it was found in.
+struct Diagnostic {
+    path: String,
+    line: usize,
+    column: usize,
+    level: u8,
+    rule: &'static str,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new(path: &str, finding: Finding) -> Self {
+        Diagnostic {
+            path: path.to_string(),
+            line: finding.line,
+            column: finding.char_column.unwrap_or(1),
+            level: finding.level,
+            rule: finding.rule,
+            message: finding.message,
+        }
+    }
+
+    fn to_text(&self) -> String {
+        format!(
+            "{}:{}:{}: {}: {}",
+            self.path, self.line, self.column, self.rule, self.message
+        )
+    }
+
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"path\":{},\"line\":{},\"column\":{},\"level\":{},\"rule\":{},\"message\":{}}}",
+            json_string(&self.path),
+            self.line,
+            self.column,
+            self.level,
+            json_string(self.rule),
+            json_string(&self.message)
+        )
+    }
+
+    fn to_sarif_result(&self) -> String {
+        format!(
+            "{{\"ruleId\":{},\"level\":\"{}\",\"message\":{{\"text\":{}}},\
+             \"locations\":[{{\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":{}}},\
+             \"region\":{{\"startLine\":{},\"startColumn\":{}}}}}}}]}}",
+            json_string(self.rule),
+            self.sarif_level(),
+            json_string(&self.message),
+            json_string(&self.path),
+            self.line,
+            self.column
+        )
+    }
+
+    /// The SARIF level of the finding: level 1 findings such as parse errors
+    /// are errors, level 2 findings warnings and level 3 findings notes.
+    fn sarif_level(&self) -> &'static str {
+        match self.level {
+            0 | 1 => "error",
+            2 => "warning",
+            _ => "note",
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Whether a file found in a directory should be checked. With the
+/// `compression` feature, this includes compressed files like `*.conllu.gz`.
+fn is_conllu(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+
+    #[cfg(feature = "compression")]
+    let name = [".gz", ".xz", ".zst"]
+        .into_iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name);
+
+    name.ends_with(".conllu")
+}
+
+struct Linter {
+    format: OutputFormat,
+    level: u8,
+    files: usize,
+    unreadable: usize,
+    problems: usize,
+    sarif_results: Vec<String>,
+}
+
+impl Linter {
+    fn lint<R: BufRead>(&mut self, reader: R, path: &str) {
+        #[cfg(feature = "diagnostics")]
+        if self.format == OutputFormat::Pretty {
+            self.lint_pretty(reader, path);
+            return;
+        }
+
+        self.files += 1;
+        let findings = Validator::with_level(self.level).validate_doc(Doc::new(reader));
+
+        for finding in findings {
+            self.problems += 1;
+            let diagnostic = Diagnostic::new(path, finding);
+            match self.format {
+                OutputFormat::Text => println!("{}", diagnostic.to_text()),
+                OutputFormat::Jsonl => println!("{}", diagnostic.to_json()),
+                OutputFormat::Sarif => self.sarif_results.push(diagnostic.to_sarif_result()),
+                #[cfg(feature = "diagnostics")]
+                OutputFormat::Pretty => unreachable!(),
+            }
+        }
+    }
+
+    /// Print findings as source snippets. This needs the lines of the input,
+    /// so it is read into memory as a whole.
+    #[cfg(feature = "diagnostics")]
+    fn lint_pretty<R: BufRead>(&mut self, mut reader: R, path: &str) {
+        use std::io::IsTerminal;
+
+        use rs_conllu::diagnostics::SnippetRenderer;
+
+        let mut source = vec![];
+        if let Err(e) = reader.read_to_end(&mut source) {
+            self.unreadable(path, e);
+            return;
+        }
+
+        self.files += 1;
+        let renderer = if io::stdout().is_terminal() {
+            SnippetRenderer::styled()
+        } else {
+            SnippetRenderer::plain()
+        };
+        let lines: Vec<&[u8]> = source.split(|&b| b == b'\n').collect();
+        let findings = Validator::with_level(self.level).validate_doc(Doc::new(&source[..]));
+
+        for finding in findings {
+            self.problems += 1;
+            let line = lines
+                .get(finding.line.wrapping_sub(1))
+                .copied()
+                .unwrap_or_default();
+            let line = String::from_utf8_lossy(line);
+            let line = line.trim_end_matches('\r');
+            println!("{}", renderer.render_finding(&finding, line, path));
+        }
+    }
+
+    fn lint_path(&mut self, path: &Path) {
+        if path == Path::new("-") {
+            #[cfg(feature = "compression")]
+            match rs_conllu::parsers::decompress(BufReader::new(io::stdin())) {
+                Ok(reader) => self.lint(reader, "<stdin>"),
+                Err(e) => self.unreadable("<stdin>", e),
+            }
+            #[cfg(not(feature = "compression"))]
+            self.lint(io::stdin().lock(), "<stdin>");
+            return;
+        }
 
-    for entry in walker {
-        let path = entry.unwrap().into_path();
         if path.is_file() {
-            if let Some(ext) = path.extension() {
-                if ext == "conllu" {
-                    println!("Parsing {path:?}");
-                    let file = File::open(path).unwrap();
-                    for s in parse_file(file) {
-                        if let Err(e) = s {
-                            println!("❌");
-                            println!("{e}");
-                        }
+            self.lint_file(path);
+            return;
+        }
+
+        for entry in walkdir::WalkDir::new(path).sort_by_file_name() {
+            match entry {
+                Ok(entry) => {
+                    let path = entry.path();
+                    if path.is_file() && is_conllu(path) {
+                        self.lint_file(path);
                     }
-                    println!()
                 }
+                Err(e) => self.unreadable(&e.path().unwrap_or(path).display().to_string(), e),
             }
         }
     }
+
+    fn lint_file(&mut self, path: &Path) {
+        let reader = File::open(path).map(BufReader::new);
+        #[cfg(feature = "compression")]
+        let reader = reader.and_then(rs_conllu::parsers::decompress);
+
+        match reader {
+            Ok(reader) => self.lint(reader, &path.display().to_string()),
+            Err(e) => self.unreadable(&path.display().to_string(), e),
+        }
+    }
+
+    fn unreadable(&mut self, path: &str, error: impl std::fmt::Display) {
+        self.unreadable += 1;
+        eprintln!("conllint: cannot read {path}: {error}");
+    }
+
+    fn print_sarif(&self) {
+        println!(
+            "{{\"version\":\"2.1.0\",\
+             \"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\
+             \"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"conllint\",\"version\":{}}}}},\
+             \"results\":[{}]}}]}}",
+            json_string(env!("CARGO_PKG_VERSION")),
+            self.sarif_results.join(",")
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = cli::LintCli::parse();
+    let mut linter = Linter {
+        format: cli.format,
+        level: cli.level,
+        files: 0,
+        unreadable: 0,
+        problems: 0,
+        sarif_results: vec![],
+    };
+
+    for path in &cli.paths {
+        linter.lint_path(path);
+    }
+
+    if cli.format == OutputFormat::Sarif {
+        linter.print_sarif();
+    }
+
+    eprintln!(
+        "Checked {} file(s): {} problem(s), {} unreadable file(s)",
+        linter.files, linter.problems, linter.unreadable
+    );
+
+    if linter.unreadable > 0 {
+        ExitCode::from(2)
+    } else if linter.problems > 0 {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn diagnostic(level: u8, message: &str) -> Diagnostic {
+        Diagnostic {
+            path: "dir\\a \"b\".conllu".to_string(),
+            line: 3,
+            column: 1,
+            level,
+            rule: "parse-error",
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_json_string() {
+        let input = "quote \" backslash \\ newline \n tab \t nul \0 bell \u{7} del \u{7f} ü 😀";
+        let escaped = json_string(input);
+
+        assert!(!escaped.chars().any(char::is_control));
+        assert_eq!(serde_json::from_str::<String>(&escaped).unwrap(), input);
+    }
+
+    #[test]
+    fn test_sarif_levels() {
+        let levels: Vec<_> = (1..=3)
+            .map(|level| {
+                let result = diagnostic(level, "Unexpected \"\\\u{1}\"").to_sarif_result();
+                let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+                assert_eq!(result["message"]["text"], "Unexpected \"\\\u{1}\"");
+                assert_eq!(
+                    result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+                    "dir\\a \"b\".conllu"
+                );
+                result["level"].as_str().unwrap().to_string()
+            })
+            .collect();
+
+        assert_eq!(levels, vec!["error", "warning", "note"]);
+    }
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&diagnostic(2, "a\tb").to_json()).unwrap();
+        assert_eq!(json["message"], "a\tb");
+        assert_eq!(json["path"], "dir\\a \"b\".conllu");
+        assert_eq!(json["level"], 2);
+    }
 }
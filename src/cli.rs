@@ -1,9 +1,35 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
+/// Check CoNLL-U files for format and annotation errors.
+///
+/// Exits with status 1 if any problem was found and with status 2 if
+/// any file could not be read.
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct LintCli {
-    pub path: PathBuf,
+    /// Files or directories to check. Directories are searched for `*.conllu`
+    /// files, `-` reads from standard input.
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+    /// The format of the reported diagnostics.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    /// The highest validation level to check.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=3))]
+    pub level: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One `path:line:column: rule: message` line per diagnostic.
+    Text,
+    /// One JSON object per diagnostic and line.
+    Jsonl,
+    /// A single SARIF 2.1.0 log.
+    Sarif,
+    /// The offending lines with the problems underlined.
+    #[cfg(feature = "diagnostics")]
+    Pretty,
 }
@@ -0,0 +1,257 @@
+use std::{fmt, slice};
+
+use crate::parsers::ParseErrorType;
+
+/// A column of a CoNLL-U or CoNLL-U Plus file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Column<'a> {
+    Id,
+    Form,
+    Lemma,
+    Upos,
+    Xpos,
+    Feats,
+    Head,
+    Deprel,
+    Deps,
+    Misc,
+    /// A column beyond the ten standard ones, as declared in the
+    /// `global.columns` header of a CoNLL-U Plus file (e.g. `PARSEME:MWE`).
+    Extra(&'a str),
+}
+
+impl<'a> Column<'a> {
+    /// Interpret a column name from a `global.columns` header.
+    /// Names of the standard columns are case sensitive, as in the specification.
+    pub fn from_name(name: &'a str) -> Column<'a> {
+        match name {
+            "ID" => Column::Id,
+            "FORM" => Column::Form,
+            "LEMMA" => Column::Lemma,
+            "UPOS" => Column::Upos,
+            "XPOS" => Column::Xpos,
+            "FEATS" => Column::Feats,
+            "HEAD" => Column::Head,
+            "DEPREL" => Column::Deprel,
+            "DEPS" => Column::Deps,
+            "MISC" => Column::Misc,
+            _ => Column::Extra(name),
+        }
+    }
+
+    /// The name of the column as it appears in a `global.columns` header.
+    pub fn name(&self) -> &'a str {
+        match self {
+            Column::Id => "ID",
+            Column::Form => "FORM",
+            Column::Lemma => "LEMMA",
+            Column::Upos => "UPOS",
+            Column::Xpos => "XPOS",
+            Column::Feats => "FEATS",
+            Column::Head => "HEAD",
+            Column::Deprel => "DEPREL",
+            Column::Deps => "DEPS",
+            Column::Misc => "MISC",
+            Column::Extra(name) => name,
+        }
+    }
+
+    /// The error for a line that ends before this column.
+    pub(crate) fn missing(&self) -> ParseErrorType {
+        match self {
+            Column::Id => ParseErrorType::MissingField("id"),
+            Column::Form => ParseErrorType::MissingField("form"),
+            Column::Lemma => ParseErrorType::MissingField("lemma"),
+            Column::Upos => ParseErrorType::MissingField("upos"),
+            Column::Xpos => ParseErrorType::MissingField("xpos"),
+            Column::Feats => ParseErrorType::MissingField("features"),
+            Column::Head => ParseErrorType::MissingField("head"),
+            Column::Deprel => ParseErrorType::MissingField("deprel"),
+            Column::Deps => ParseErrorType::MissingField("deps"),
+            Column::Misc => ParseErrorType::MissingField("misc"),
+            Column::Extra(name) => ParseErrorType::MissingColumn(name.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Column<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+const STANDARD: [Column<'static>; 10] = [
+    Column::Id,
+    Column::Form,
+    Column::Lemma,
+    Column::Upos,
+    Column::Xpos,
+    Column::Feats,
+    Column::Head,
+    Column::Deprel,
+    Column::Deps,
+    Column::Misc,
+];
+
+/// The ordered list of columns of a document. Plain CoNLL-U files always
+/// have the ten [standard](Columns::standard) columns, while CoNLL-U Plus files
+/// declare their columns in a `# global.columns = ...` comment at the
+/// beginning of the file.
+///
+/// ```rust
+/// use rs_conllu::columns::{Column, Columns};
+///
+/// let columns = Columns::parse("ID FORM UPOS HEAD DEPREL MISC PARSEME:MWE").unwrap();
+///
+/// assert_eq!(columns.len(), 7);
+/// assert_eq!(columns.position(Column::Upos), Some(2));
+/// assert_eq!(columns.extra().collect::<Vec<_>>(), vec!["PARSEME:MWE"]);
+/// assert_eq!(columns.to_string(), "ID FORM UPOS HEAD DEPREL MISC PARSEME:MWE");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Columns<'a> {
+    columns: ColumnList<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum ColumnList<'a> {
+    Standard,
+    Declared(Vec<Column<'a>>),
+}
+
+impl<'a> ColumnList<'a> {
+    fn as_slice(&self) -> &[Column<'a>] {
+        match self {
+            ColumnList::Standard => &STANDARD,
+            ColumnList::Declared(columns) => columns,
+        }
+    }
+}
+
+impl PartialEq for Columns<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.columns.as_slice() == other.columns.as_slice()
+    }
+}
+
+impl Eq for Columns<'_> {}
+
+impl<'a> Columns<'a> {
+    /// The ten columns defined by the CoNLL-U specification.
+    pub fn standard() -> Columns<'static> {
+        Columns {
+            columns: ColumnList::Standard,
+        }
+    }
+
+    /// Parse the value of a `global.columns` header. The columns are separated
+    /// by whitespace, must contain `ID` and must not repeat.
+    pub fn parse(header: &'a str) -> Result<Columns<'a>, ParseErrorType> {
+        let mut columns: Vec<Column<'a>> = Vec::new();
+
+        for column in header.split_whitespace().map(Column::from_name) {
+            if columns.contains(&column) {
+                return Err(ParseErrorType::InvalidColumns(format!(
+                    "duplicate column {column}"
+                )));
+            }
+            columns.push(column);
+        }
+
+        if !columns.contains(&Column::Id) {
+            return Err(ParseErrorType::InvalidColumns(
+                "missing column ID".to_string(),
+            ));
+        }
+
+        Ok(Columns {
+            columns: ColumnList::Declared(columns),
+        })
+    }
+
+    /// Whether these are exactly the ten standard columns in standard order.
+    pub fn is_standard(&self) -> bool {
+        self.columns.as_slice() == STANDARD
+    }
+
+    /// The 0-based position of a column.
+    pub fn position(&self, column: Column<'_>) -> Option<usize> {
+        self.columns.as_slice().iter().position(|c| *c == column)
+    }
+
+    /// Iterate over the names of the non-standard columns.
+    pub fn extra(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.columns.as_slice().iter().filter_map(|c| match c {
+            Column::Extra(name) => Some(*name),
+            _ => None,
+        })
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Column<'a>> {
+        self.columns.as_slice().iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.as_slice().is_empty()
+    }
+}
+
+impl Default for Columns<'_> {
+    fn default() -> Self {
+        Columns::standard()
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b Columns<'a> {
+    type Item = &'b Column<'a>;
+    type IntoIter = slice::Iter<'b, Column<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.columns.as_slice().iter()
+    }
+}
+
+impl fmt::Display for Columns<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, column) in self.columns.as_slice().iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{column}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_standard_header() {
+        let columns =
+            Columns::parse("ID FORM LEMMA UPOS XPOS FEATS HEAD DEPREL DEPS MISC").unwrap();
+        assert!(columns.is_standard());
+        assert_eq!(columns, Columns::standard());
+    }
+
+    #[test]
+    fn test_invalid_headers() {
+        assert_eq!(
+            Columns::parse("FORM LEMMA"),
+            Err(ParseErrorType::InvalidColumns(
+                "missing column ID".to_string()
+            ))
+        );
+        assert_eq!(
+            Columns::parse("ID FORM FORM"),
+            Err(ParseErrorType::InvalidColumns(
+                "duplicate column FORM".to_string()
+            ))
+        );
+    }
+}
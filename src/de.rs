@@ -0,0 +1,512 @@
+//! Deserialize tokens into user-defined types with serde. Requires the `serde`
+//! feature.
+//!
+//! Each token line is presented to serde as a map from column names to fields.
+//! The standard columns are named in lowercase (`id`, `form`, `lemma`, `upos`,
+//! `xpos`, `feats`, `head`, `deprel`, `deps` and `misc`), while extra columns of
+//! CoNLL-U Plus files keep their declared name. When deserializing a struct,
+//! columns without a matching field are skipped without being allocated.
+//!
+//! Fields are converted as follows:
+//!
+//! - `_` is `None` for [Option]s and the string `"_"` otherwise. As in the
+//!   parser, a LEMMA `_` is a literal underscore if the FORM is `_` as well.
+//! - Numbers and booleans are parsed from the field.
+//! - Maps, e.g. for [Features](crate::token::Features), are read from
+//!   `key=value` pairs separated by `|`.
+//! - Sequences are read from items separated by `|`.
+//! - Enums with unit variants are matched by name.
+//!
+//! ```rust
+//! use rs_conllu::{parsers::Doc, TokenID, UPOS};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Word {
+//!     form: String,
+//!     upos: Option<UPOS>,
+//!     head: Option<TokenID>,
+//!     #[serde(rename = "PARSEME:MWE")]
+//!     mwe: Option<String>,
+//! }
+//!
+//! let input = "# global.columns = ID FORM UPOS HEAD PARSEME:MWE
+//! 1\tkicked\tVERB\t0\t1:VID
+//! 2\tthe\tDET\t3\t1
+//! 3\tbucket\tNOUN\t1\t_
+//! ";
+//!
+//! let sentences: Vec<_> = Doc::new(input.as_bytes())
+//!     .deserialize::<Word>()
+//!     .collect::<Result<_, _>>()
+//!     .unwrap();
+//!
+//! let words = &sentences[0].tokens;
+//! assert_eq!(words[2].form, "bucket");
+//! assert_eq!(words[2].upos, Some(UPOS::NOUN));
+//! assert_eq!(words[2].head, Some(TokenID::Single(1)));
+//! assert_eq!(words[0].mwe.as_deref(), Some("1:VID"));
+//! assert_eq!(words[2].mwe, None);
+//! ```
+
+use std::{fmt, io::BufRead, marker::PhantomData};
+
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer, StrDeserializer},
+        DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+use thiserror::Error;
+
+use crate::{
+    columns::{Column, Columns},
+    parsers::{comment_columns, Doc, DocError},
+    Metadata,
+};
+
+/// An error that occurs while deserializing the sentences of a [Doc].
+#[derive(Error, Debug)]
+pub enum DeserializeError {
+    /// The sentence could not be read.
+    #[error(transparent)]
+    Doc(#[from] DocError),
+    /// A token line could not be deserialized into the requested type.
+    #[error("Failed to deserialize token in line {line}: {message}")]
+    Token { line: usize, message: String },
+}
+
+/// A sentence whose tokens were deserialized into a user-defined type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deserialized<D> {
+    pub meta: Metadata,
+    pub tokens: Vec<D>,
+}
+
+/// An iterator over the sentences of a [Doc] with tokens deserialized into `D`,
+/// see [Doc::deserialize].
+pub struct DeserializeSentences<T: BufRead, D> {
+    doc: Doc<T>,
+    token_type: PhantomData<D>,
+}
+
+impl<T: BufRead> Doc<T> {
+    /// Deserialize the tokens of every sentence into `D`, mapping columns to
+    /// fields by name. See the [de](crate::de) module for details.
+    pub fn deserialize<D: DeserializeOwned>(self) -> DeserializeSentences<T, D> {
+        DeserializeSentences {
+            doc: self,
+            token_type: PhantomData,
+        }
+    }
+}
+
+impl<T: BufRead, D: DeserializeOwned> Iterator for DeserializeSentences<T, D> {
+    type Item = Result<Deserialized<D>, DeserializeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let buffer = match self.doc.read_sentence()? {
+            Ok(buffer) => buffer,
+            Err(e) => return Some(Err(e.into())),
+        };
+        let sentence = deserialize_sentence(
+            &buffer,
+            self.doc.state.columns.columns(),
+            self.doc.sentence_line(),
+        );
+        self.doc.state.columns.update_from(&buffer);
+
+        Some(sentence)
+    }
+}
+
+/// Deserialize the tokens of the sentence in `buffer`, whose first line is
+/// `first_line`, with the columns declared before the sentence.
+fn deserialize_sentence<'a, D: DeserializeOwned>(
+    buffer: &'a str,
+    mut columns: Columns<'a>,
+    first_line: usize,
+) -> Result<Deserialized<D>, DeserializeError> {
+    let mut meta = Metadata::new();
+    let mut tokens = vec![];
+    for (i, line) in buffer.lines().enumerate() {
+        if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim_start();
+            if let Some(header) = comment_columns(line, comment) {
+                columns = header.map_err(|e| DocError::Parse(e.at_line(first_line + i)))?;
+            }
+            meta.push_comment(comment);
+            continue;
+        }
+        if !line.is_empty() {
+            let token = D::deserialize(TokenDeserializer::new(line, &columns)).map_err(|e| {
+                DeserializeError::Token {
+                    line: first_line + i,
+                    message: e.0,
+                }
+            })?;
+            tokens.push(token);
+        }
+    }
+
+    Ok(Deserialized { meta, tokens })
+}
+
+/// The error of the deserializers in this module, converted into
+/// [DeserializeError::Token] together with the line number.
+#[derive(Debug)]
+struct TokenError(String);
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+impl de::Error for TokenError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TokenError(msg.to_string())
+    }
+}
+
+/// The name of a column in the map presented to serde.
+fn key<'c>(column: &Column<'c>) -> &'c str {
+    match column {
+        Column::Id => "id",
+        Column::Form => "form",
+        Column::Lemma => "lemma",
+        Column::Upos => "upos",
+        Column::Xpos => "xpos",
+        Column::Feats => "feats",
+        Column::Head => "head",
+        Column::Deprel => "deprel",
+        Column::Deps => "deps",
+        Column::Misc => "misc",
+        Column::Extra(name) => name,
+    }
+}
+
+/// Deserializes a token line as a map from column names to fields.
+struct TokenDeserializer<'a, 'c> {
+    fields: std::str::Split<'a, char>,
+    columns: std::slice::Iter<'c, Column<'c>>,
+    wanted: Option<&'static [&'static str]>,
+    value: Option<FieldDeserializer<'a>>,
+    literal_lemma: bool,
+}
+
+impl<'a, 'c> TokenDeserializer<'a, 'c> {
+    fn new(line: &'a str, columns: &'c Columns<'c>) -> Self {
+        let field = |column| {
+            let index = columns.iter().position(|&c| c == column)?;
+            line.split('\t').nth(index)
+        };
+        // by convention, LEMMA `_` is the literal lemma of the FORM `_`
+        let literal_lemma = field(Column::Form) == Some("_")
+            && field(Column::Lemma) == Some("_")
+            && field(Column::Id).is_some_and(|id| !id.contains('-'));
+
+        TokenDeserializer {
+            fields: line.split('\t'),
+            columns: columns.iter(),
+            wanted: None,
+            value: None,
+            literal_lemma,
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for TokenDeserializer<'_, '_> {
+    type Error = TokenError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.wanted = Some(fields);
+        visitor.visit_map(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for TokenDeserializer<'_, '_> {
+    type Error = TokenError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        for column in self.columns.by_ref() {
+            let Some(field) = self.fields.next() else {
+                return Err(TokenError(format!("missing column {column}")));
+            };
+            let key = key(column);
+            if self.wanted.is_some_and(|wanted| !wanted.contains(&key)) {
+                continue;
+            }
+            self.value = Some(FieldDeserializer {
+                field,
+                literal: self.literal_lemma && *column == Column::Lemma,
+            });
+            return seed.deserialize(StrDeserializer::new(key)).map(Some);
+        }
+        if let Some(field) = self.fields.next() {
+            return Err(TokenError(format!(
+                "unexpected field {field:?} after the last column"
+            )));
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let field = self.value.take().expect("value requested before key");
+        seed.deserialize(field)
+    }
+}
+
+/// Deserializes a single field of a token line.
+struct FieldDeserializer<'a> {
+    field: &'a str,
+    /// Whether `_` is a literal underscore rather than an empty field.
+    literal: bool,
+}
+
+impl<'a> FieldDeserializer<'a> {
+    fn new(field: &'a str) -> Self {
+        FieldDeserializer {
+            field,
+            literal: false,
+        }
+    }
+
+    fn parse<T>(&self) -> Result<T, TokenError>
+    where
+        T: std::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        self.field
+            .parse()
+            .map_err(|e| TokenError(format!("invalid value {:?}: {e}", self.field)))
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for FieldDeserializer<'_> {
+    type Error = TokenError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.field)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.field {
+            "_" if !self.literal => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let items = self.field.split('|').map(FieldDeserializer::new);
+        SeqDeserializer::new(items).deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let pairs = self
+            .field
+            .split('|')
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+            .map(|(key, value)| (FieldDeserializer::new(key), FieldDeserializer::new(value)));
+        MapDeserializer::new(pairs).deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        StrDeserializer::new(self.field).deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit unit_struct tuple
+        tuple_struct struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, TokenError> for FieldDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{token::Features, TokenID, UPOS};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Word {
+        id: TokenID,
+        form: String,
+        upos: UPOS,
+        feats: Option<Features>,
+        head: Option<usize>,
+        deps: Vec<String>,
+    }
+
+    #[test]
+    fn test_deserialize_struct() {
+        let input = "# sent_id = 1
+1\tThey\tthey\tPRON\tPRP\tCase=Nom|Number=Plur\t2\tnsubj\t2:nsubj|4:nsubj\t_
+2\tbuy\tbuy\tVERB\tVBP\t_\t0\troot\t0:root\t_
+";
+        let sentences: Vec<_> = Doc::new(input.as_bytes())
+            .deserialize::<Word>()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(sentences[0].meta.sent_id(), Some("1"));
+        assert_eq!(
+            sentences[0].tokens[0],
+            Word {
+                id: TokenID::Single(1),
+                form: "They".to_string(),
+                upos: UPOS::PRON,
+                feats: Some("Case=Nom|Number=Plur".parse().unwrap()),
+                head: Some(2),
+                deps: vec!["2:nsubj".to_string(), "4:nsubj".to_string()],
+            }
+        );
+        assert_eq!(sentences[0].tokens[1].feats, None);
+    }
+
+    #[test]
+    fn test_deserialize_literal_underscore() {
+        #[derive(Deserialize)]
+        struct Lemma {
+            lemma: Option<String>,
+        }
+
+        let input = "1-2\t_\t_\t_\t_\t_\t_\t_\t_\t_
+1\t_\t_\tPUNCT\t_\t_\t0\troot\t_\t_
+2\t_\tbe\tAUX\t_\t_\t1\tcop\t_\t_
+3\tfine\t_\tADJ\t_\t_\t1\tamod\t_\t_
+";
+        let sentences: Vec<_> = Doc::new(input.as_bytes())
+            .deserialize::<Lemma>()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let lemmas: Vec<_> = sentences[0]
+            .tokens
+            .iter()
+            .map(|t| t.lemma.as_deref())
+            .collect();
+        assert_eq!(lemmas, vec![None, Some("_"), Some("be"), None]);
+    }
+
+    #[test]
+    fn test_deserialize_map() {
+        let input = "# global.columns = ID FORM PARSEME:MWE
+1\tkicked\t1:VID
+";
+        let sentences: Vec<_> = Doc::new(input.as_bytes())
+            .deserialize::<HashMap<String, String>>()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let token = &sentences[0].tokens[0];
+        assert_eq!(token.len(), 3);
+        assert_eq!(token["PARSEME:MWE"], "1:VID");
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let input = "1\tThey\tthey\tPRON\tPRP\t_\tx\tnsubj\t_\t_
+
+1\tbuy\tbuy\tVERB\tVBP\t_\t0
+";
+        let results: Vec<_> = Doc::new(input.as_bytes()).deserialize::<Word>().collect();
+
+        assert_eq!(
+            results[0].as_ref().unwrap_err().to_string(),
+            "Failed to deserialize token in line 1: invalid value \"x\": invalid digit found in string"
+        );
+        assert_eq!(
+            results[1].as_ref().unwrap_err().to_string(),
+            "Failed to deserialize token in line 3: missing column DEPREL"
+        );
+
+        let input = "1\tThey\tthey\tPRON\tPRP\t_\t0\troot\t_\t_\tEXTRA\n";
+        let results: Vec<_> = Doc::new(input.as_bytes()).deserialize::<Word>().collect();
+        assert_eq!(
+            results[0].as_ref().unwrap_err().to_string(),
+            "Failed to deserialize token in line 1: unexpected field \"EXTRA\" after the last column"
+        );
+
+        let input = "# sent_id = 1\n# global.columns = FORM LEMMA\n1\tThey\n";
+        let results: Vec<_> = Doc::new(input.as_bytes()).deserialize::<Word>().collect();
+        let Err(DeserializeError::Doc(DocError::Parse(error))) = &results[0] else {
+            panic!("expected a parse error, got {:?}", results[0]);
+        };
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.text(), "FORM LEMMA");
+    }
+}
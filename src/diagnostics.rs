@@ -0,0 +1,211 @@
+//! Render parse errors and validation findings as annotated source snippets
+//! for terminal output. Requires the `diagnostics` feature.
+//!
+//! ```rust
+//! use rs_conllu::{diagnostics::SnippetRenderer, parse_sentence};
+//!
+//! let error = parse_sentence("1\tcats\tcat\tNOUN\t_\tNumber\t0\troot\t_\t_").unwrap_err();
+//! let rendered = SnippetRenderer::plain().render_parse_error(&error, "cats.conllu");
+//!
+//! assert!(rendered.contains("cats.conllu"));
+//! assert!(rendered.contains("^^^^^^ FEATS"));
+//! ```
+
+use std::{iter, ops::Range};
+
+use annotate_snippets::{Level, Message, Renderer, Snippet};
+
+use crate::{
+    parsers::{ConlluParseError, ParseErrorType},
+    validate::Finding,
+};
+
+/// Renders errors as the offending line with the bad field underlined.
+pub struct SnippetRenderer {
+    renderer: Renderer,
+}
+
+impl SnippetRenderer {
+    /// A renderer without colors.
+    pub fn plain() -> Self {
+        SnippetRenderer {
+            renderer: Renderer::plain(),
+        }
+    }
+
+    /// A renderer that colors its output with ANSI escape codes.
+    pub fn styled() -> Self {
+        SnippetRenderer {
+            renderer: Renderer::styled(),
+        }
+    }
+
+    /// Render a parse error from the file or input named `origin`.
+    pub fn render_parse_error(&self, error: &ConlluParseError, origin: &str) -> String {
+        let title = error.kind().to_string();
+        let label = error.column_name().unwrap_or("here");
+        let hint = hint(error);
+
+        let (source, span) = expand_tabs(error.line_text(), error.span());
+
+        let mut message = Level::Error.title(&title).snippet(
+            Snippet::source(&source)
+                .line_start(error.line())
+                .origin(origin)
+                .annotation(Level::Error.span(visible(span)).label(label)),
+        );
+        if let Some(hint) = &hint {
+            message = message.footer(Level::Help.title(hint));
+        }
+
+        self.render(message)
+    }
+
+    /// Render a validation finding in `line_text`, the line the finding refers to,
+    /// from the file or input named `origin`.
+    pub fn render_finding(&self, finding: &Finding, line_text: &str, origin: &str) -> String {
+        let (source, span) = expand_tabs(line_text, finding_span(finding, line_text));
+        let note = match &finding.sent_id {
+            Some(sent_id) => format!("validation level {}, sentence {sent_id}", finding.level),
+            None => format!("validation level {}", finding.level),
+        };
+
+        let message = Level::Error
+            .title(&finding.message)
+            .snippet(
+                Snippet::source(&source)
+                    .line_start(finding.line)
+                    .origin(origin)
+                    .annotation(Level::Error.span(visible(span)).label(finding.rule)),
+            )
+            .footer(Level::Note.title(&note));
+
+        self.render(message)
+    }
+
+    fn render(&self, message: Message) -> String {
+        self.renderer.render(message).to_string()
+    }
+}
+
+/// Replace the tabs between the fields with spaces and move `span` accordingly,
+/// since carets are not aligned correctly below tabs.
+fn expand_tabs(line: &str, span: Range<usize>) -> (String, Range<usize>) {
+    let shift = |offset: usize| offset + line[..offset].matches('\t').count() * (TAB_WIDTH - 1);
+    let expanded = line.replace('\t', &" ".repeat(TAB_WIDTH));
+    (expanded, shift(span.start)..shift(span.end))
+}
+
+const TAB_WIDTH: usize = 4;
+
+/// Empty spans, e.g. for missing fields, are widened so that a caret is shown.
+fn visible(span: Range<usize>) -> Range<usize> {
+    if span.is_empty() {
+        span.start..span.start + 1
+    } else {
+        span
+    }
+}
+
+/// The field at the column of the finding, or the whole line.
+fn finding_span(finding: &Finding, line_text: &str) -> Range<usize> {
+    let start = finding.char_column.and_then(|column| {
+        line_text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(iter::once(line_text.len()))
+            .nth(column.checked_sub(1)?)
+    });
+
+    match start {
+        Some(start) => {
+            let end = line_text[start..]
+                .find('\t')
+                .map_or(line_text.len(), |end| start + end);
+            start..end
+        }
+        None => 0..line_text.len(),
+    }
+}
+
+fn hint(error: &ConlluParseError) -> Option<String> {
+    let hint = match error.kind() {
+        ParseErrorType::MissingField(_)
+        | ParseErrorType::MissingColumn(_)
+        | ParseErrorType::ExtraField(_) => {
+            "token lines need one tab-separated field per column; use `_` for empty fields"
+        }
+        ParseErrorType::FailedIdParse(_) => {
+            "IDs are integers, ranges like `1-2` or empty nodes like `1.1`"
+        }
+        ParseErrorType::FailedUposParse { .. } => {
+            "UPOS must be one of the 17 universal part-of-speech tags"
+        }
+        ParseErrorType::KeyValueParseError => match error.column_name() {
+            Some("DEPS") => "enhanced dependencies are written as `head:deprel`, separated by `|`",
+            _ => "features are written as `Name=Value`, separated by `|`",
+        },
+        ParseErrorType::DuplicateFeature(name) => {
+            return Some(format!(
+                "join the values of {name} with a comma, e.g. `{name}=A,B`"
+            ))
+        }
+        ParseErrorType::InvalidColumns(_) => {
+            "global.columns must include ID and may not repeat a column"
+        }
+    };
+    Some(hint.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse_sentence, validate::Validator, Sentence};
+
+    use super::*;
+
+    #[test]
+    fn test_render_missing_field() {
+        let error = parse_sentence("1\tdog\tdog\tNOUN").unwrap_err();
+        let rendered = SnippetRenderer::plain().render_parse_error(&error, "dog.conllu");
+
+        assert!(rendered.starts_with("error: Missing field: xpos"));
+        assert!(rendered.contains("1 | 1    dog    dog    NOUN\n"));
+        assert!(rendered.contains("  |                        ^ XPOS"));
+        assert!(rendered.contains("help: token lines need"));
+    }
+
+    #[test]
+    fn test_render_finding() {
+        let line = "1\tdog\tdog\tNOUN\t_\t_\t1\troot\t_\t_";
+        let sentence: Sentence = parse_sentence(line).unwrap();
+        let findings = Validator::with_level(2).validate_sentence(&sentence, 1);
+        let finding = findings
+            .iter()
+            .find(|f| f.rule == "head-self-loop")
+            .unwrap();
+
+        let rendered = SnippetRenderer::plain().render_finding(finding, line, "dog.conllu");
+
+        assert!(rendered.contains("head-self-loop"));
+        assert!(rendered.contains("note: validation level 2"));
+    }
+
+    #[test]
+    fn test_finding_span_out_of_range() {
+        let line = "1\tdog";
+        let mut finding = Finding {
+            level: 1,
+            rule: "test",
+            sent_id: None,
+            line: 1,
+            char_column: Some(0),
+            message: String::new(),
+        };
+
+        assert_eq!(finding_span(&finding, line), 0..line.len());
+        finding.char_column = Some(3);
+        assert_eq!(finding_span(&finding, line), 2..line.len());
+        finding.char_column = Some(10);
+        assert_eq!(finding_span(&finding, line), 0..line.len());
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+
+use crate::{Sentence, TokenID};
+
+/// A labeled edge of the [EnhancedGraph].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge<'a> {
+    pub head: TokenID,
+    pub dependent: TokenID,
+    pub rel: &'a str,
+}
+
+/// The enhanced dependency graph of a [Sentence], as given by the DEPS column.
+/// Created via [Sentence::enhanced_graph].
+///
+/// Nodes are the words and empty nodes of the sentence, identified by their
+/// [TokenID]. The artificial root node is [TokenID::Single(0)](TokenID::Single);
+/// it is not returned by [nodes](EnhancedGraph::nodes) but can be used in all queries.
+/// Multiword tokens are not part of the graph.
+///
+/// ```rust
+/// use rs_conllu::{parse_sentence, TokenID};
+///
+/// let sentence = parse_sentence(
+///     "1\tSue\tSue\tPROPN\t_\t_\t2\tnsubj\t2:nsubj|3:nsubj\t_
+/// 2\tlikes\tlike\tVERB\t_\t_\t0\troot\t0:root\t_
+/// 3\tcoffee\tcoffee\tNOUN\t_\t_\t2\tobj\t2:obj\t_
+/// ",
+/// )
+/// .unwrap();
+/// let graph = sentence.enhanced_graph();
+///
+/// assert_eq!(graph.incoming(TokenID::Single(1)).count(), 2);
+/// assert_eq!(graph.outgoing(TokenID::Single(2)).map(|e| e.rel).collect::<Vec<_>>(), vec!["nsubj", "obj"]);
+/// assert!(graph.unreachable_from_root().is_empty());
+/// assert_eq!(
+///     graph.shortest_path(TokenID::Single(1), TokenID::Single(3)),
+///     Some(vec![TokenID::Single(1), TokenID::Single(3)])
+/// );
+/// ```
+pub struct EnhancedGraph<'a> {
+    nodes: Vec<TokenID>,
+    edges: Vec<Edge<'a>>,
+    outgoing: HashMap<TokenID, Vec<usize>>,
+    incoming: HashMap<TokenID, Vec<usize>>,
+}
+
+const ROOT: TokenID = TokenID::Single(0);
+
+impl<'a> EnhancedGraph<'a> {
+    pub fn new(sentence: &'a Sentence) -> Self {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+
+        for token in &sentence.tokens {
+            if let TokenID::Range(_, _) = token.id {
+                continue;
+            }
+            nodes.push(token.id);
+            for dep in token.deps.iter().flatten() {
+                edges.push(Edge {
+                    head: dep.head,
+                    dependent: token.id,
+                    rel: &dep.rel,
+                });
+            }
+        }
+
+        let mut outgoing: HashMap<TokenID, Vec<usize>> = HashMap::new();
+        let mut incoming: HashMap<TokenID, Vec<usize>> = HashMap::new();
+        for (i, edge) in edges.iter().enumerate() {
+            outgoing.entry(edge.head).or_default().push(i);
+            incoming.entry(edge.dependent).or_default().push(i);
+        }
+
+        EnhancedGraph {
+            nodes,
+            edges,
+            outgoing,
+            incoming,
+        }
+    }
+
+    /// The words and empty nodes in sentence order.
+    pub fn nodes(&self) -> &[TokenID] {
+        &self.nodes
+    }
+
+    /// All edges in sentence order of their dependents.
+    pub fn edges(&self) -> &[Edge<'a>] {
+        &self.edges
+    }
+
+    /// The edges pointing to a node.
+    pub fn incoming(&self, id: TokenID) -> impl Iterator<Item = &Edge<'a>> {
+        Self::lookup(&self.incoming, id).map(|i| &self.edges[*i])
+    }
+
+    /// The edges starting at a node.
+    pub fn outgoing(&self, id: TokenID) -> impl Iterator<Item = &Edge<'a>> {
+        Self::lookup(&self.outgoing, id).map(|i| &self.edges[*i])
+    }
+
+    /// The edges between two nodes, in either direction.
+    pub fn edges_between(&self, a: TokenID, b: TokenID) -> impl Iterator<Item = &Edge<'a>> {
+        self.outgoing(a)
+            .filter(move |e| e.dependent == b)
+            .chain(self.outgoing(b).filter(move |e| e.dependent == a && a != b))
+    }
+
+    fn lookup(index: &HashMap<TokenID, Vec<usize>>, id: TokenID) -> std::slice::Iter<'_, usize> {
+        index.get(&id).map(Vec::as_slice).unwrap_or_default().iter()
+    }
+
+    /// The nodes that cannot be reached from the root by following the edges.
+    pub fn unreachable_from_root(&self) -> Vec<TokenID> {
+        let mut reached = HashSet::from([ROOT]);
+        let mut stack = vec![ROOT];
+
+        while let Some(node) = stack.pop() {
+            for edge in self.outgoing(node) {
+                if reached.insert(edge.dependent) {
+                    stack.push(edge.dependent);
+                }
+            }
+        }
+
+        self.nodes
+            .iter()
+            .filter(|n| !reached.contains(n))
+            .copied()
+            .collect()
+    }
+
+    /// Find a directed cycle. The returned nodes form a path where each node
+    /// is the head of the next one and the last node is the head of the first.
+    pub fn find_cycle(&self) -> Option<Vec<TokenID>> {
+        // nodes on the current path map to their position in `path`,
+        // nodes that are completely explored map to `None`
+        let mut visited: HashMap<TokenID, Option<usize>> = HashMap::new();
+
+        for start in std::iter::once(ROOT).chain(self.nodes.iter().copied()) {
+            if visited.contains_key(&start) {
+                continue;
+            }
+
+            let mut path = vec![start];
+            let mut pending = vec![self.outgoing(start)];
+            visited.insert(start, Some(0));
+
+            while let Some(edges) = pending.last_mut() {
+                match edges.next() {
+                    Some(edge) => match visited.get(&edge.dependent) {
+                        Some(Some(position)) => return Some(path[*position..].to_vec()),
+                        Some(None) => {}
+                        None => {
+                            visited.insert(edge.dependent, Some(path.len()));
+                            path.push(edge.dependent);
+                            pending.push(self.outgoing(edge.dependent));
+                        }
+                    },
+                    None => {
+                        pending.pop();
+                        if let Some(node) = path.pop() {
+                            visited.insert(node, None);
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Whether the graph contains a directed cycle.
+    pub fn has_cycle(&self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    /// The shortest path between two nodes, ignoring the direction of the edges,
+    /// including both end points. Returns `None` if the nodes are not connected.
+    pub fn shortest_path(&self, from: TokenID, to: TokenID) -> Option<Vec<TokenID>> {
+        let mut predecessors: HashMap<TokenID, TokenID> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        predecessors.insert(from, from);
+
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while current != from {
+                    current = predecessors[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let neighbours = self
+                .outgoing(node)
+                .map(|e| e.dependent)
+                .chain(self.incoming(node).map(|e| e.head));
+            for neighbour in neighbours {
+                if let Entry::Vacant(entry) = predecessors.entry(neighbour) {
+                    entry.insert(node);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse_sentence, TokenID};
+
+    #[test]
+    fn test_empty_nodes() {
+        let sentence = parse_sentence(
+            "1\tI\tI\tPRON\t_\t_\t2\tnsubj\t2:nsubj|2.1:nsubj\t_
+2\tlike\tlike\tVERB\t_\t_\t0\troot\t0:root\t_
+3\ttea\ttea\tNOUN\t_\t_\t2\tobj\t2:obj\t_
+4\tand\tand\tCCONJ\t_\t_\t6\tcc\t2.1:cc\t_
+2.1\tlike\tlike\tVERB\t_\t_\t_\t_\t2:conj\t_
+5\tyou\tyou\tPRON\t_\t_\t6\tnsubj\t2.1:nsubj\t_
+6\tcoffee\tcoffee\tNOUN\t_\t_\t3\tconj\t2.1:obj\t_
+",
+        )
+        .unwrap();
+        let graph = sentence.enhanced_graph();
+
+        assert_eq!(
+            graph
+                .outgoing(TokenID::Empty(2, 1))
+                .map(|e| e.dependent)
+                .collect::<Vec<_>>(),
+            vec![
+                TokenID::Single(1),
+                TokenID::Single(4),
+                TokenID::Single(5),
+                TokenID::Single(6)
+            ]
+        );
+        assert!(graph.unreachable_from_root().is_empty());
+        assert!(!graph.has_cycle());
+        assert_eq!(
+            graph.shortest_path(TokenID::Single(3), TokenID::Single(6)),
+            Some(vec![
+                TokenID::Single(3),
+                TokenID::Single(2),
+                TokenID::Empty(2, 1),
+                TokenID::Single(6)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cycle_and_unreachable() {
+        let sentence = parse_sentence(
+            "1\ta\t_\t_\t_\t_\t0\troot\t0:root\t_
+2\tb\t_\t_\t_\t_\t1\tdep\t3:dep\t_
+3\tc\t_\t_\t_\t_\t1\tdep\t2:dep\t_
+",
+        )
+        .unwrap();
+        let graph = sentence.enhanced_graph();
+
+        assert_eq!(
+            graph.unreachable_from_root(),
+            vec![TokenID::Single(2), TokenID::Single(3)]
+        );
+        assert_eq!(
+            graph.find_cycle(),
+            Some(vec![TokenID::Single(2), TokenID::Single(3)])
+        );
+        assert_eq!(
+            graph.shortest_path(TokenID::Single(1), TokenID::Single(2)),
+            None
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! An index of the sentences of a file by `sent_id`, for reading single
+//! sentences without parsing the whole file.
+//!
+//! ```rust
+//! use std::io::Cursor;
+//!
+//! use rs_conllu::index::Index;
+//!
+//! let conllu = "# sent_id = a
+//! 1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_
+//!
+//! ## sent_id = b
+//! 1\tWorld\tworld\tNOUN\t_\t_\t0\troot\t_\t_
+//! ";
+//!
+//! let index = Index::build(conllu.as_bytes()).unwrap();
+//! assert_eq!(index.offset("b"), Some(49));
+//!
+//! let mut reader = Cursor::new(conllu);
+//! let sentence = index.get(&mut reader, "b").unwrap().unwrap();
+//! assert_eq!(sentence.tokens[0].form, "World");
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    columns::Columns,
+    parsers::{global_columns, DeclaredColumns, Doc, DocError},
+    Metadata, Sentence,
+};
+
+/// The position of a sentence within a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// The byte offset of the sentence.
+    pub offset: u64,
+    /// The 1-based line number of the first line of the sentence.
+    pub line: usize,
+    /// The `global.columns` declaration in effect before the sentence.
+    columns: Option<usize>,
+}
+
+/// Maps the `sent_id`s of a file to the positions of their sentences.
+///
+/// If a `sent_id` occurs more than once, the first sentence is indexed.
+/// Sentences without a `sent_id` are not indexed.
+///
+/// An index can be saved to a sidecar file next to the indexed file, see
+/// [sidecar_path](Index::sidecar_path). The sidecar file has one line per
+/// sentence, containing the `sent_id`, the byte offset and the line number
+/// separated by tabs, preceded by a `# global.columns = ...` line whenever the
+/// declared columns change. Backslashes, tabs and line breaks in a `sent_id`
+/// are escaped as `\\`, `\t`, `\n` and `\r`, and a leading `#` as `\#`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Index {
+    ids: Vec<String>,
+    entries: HashMap<String, IndexEntry>,
+    headers: Vec<String>,
+}
+
+impl Index {
+    /// Scan a document and record the position of every sentence. Only the
+    /// comment lines of the sentences are read, the token lines are not parsed.
+    /// Invalid token lines and invalid UTF-8 do not prevent indexing, only I/O
+    /// errors do.
+    pub fn build<R: BufRead>(reader: R) -> Result<Index, DocError> {
+        let mut index = Index::default();
+        let mut doc = Doc::new(reader).lossy(true);
+
+        while let Some(buffer) = doc.read_sentence() {
+            let buffer = buffer?;
+            let columns = doc.state.columns.header().and_then(|h| index.columns_id(h));
+
+            let mut meta = Metadata::new();
+            buffer
+                .lines()
+                .map_while(|line| line.strip_prefix('#'))
+                .for_each(|comment| meta.push_comment(comment.trim_start()));
+            if let Some(sent_id) = meta.sent_id() {
+                index.insert(
+                    sent_id,
+                    IndexEntry {
+                        offset: doc.sentence_offset() as u64,
+                        line: doc.sentence_line(),
+                        columns,
+                    },
+                );
+            }
+
+            doc.state.columns.update_from(&buffer);
+        }
+
+        Ok(index)
+    }
+
+    /// Build the index of the file at `path`.
+    pub fn build_file(path: impl AsRef<Path>) -> Result<Index, DocError> {
+        Index::build(BufReader::new(File::open(path)?))
+    }
+
+    /// The id of a `global.columns` declaration, or `None` for the standard
+    /// columns, which are in effect without a declaration.
+    fn columns_id(&mut self, header: &str) -> Option<usize> {
+        if Columns::parse(header).is_ok_and(|c| c.is_standard()) {
+            return None;
+        }
+        match self.headers.iter().position(|h| h == header) {
+            Some(id) => Some(id),
+            None => {
+                self.headers.push(header.to_string());
+                Some(self.headers.len() - 1)
+            }
+        }
+    }
+
+    fn insert(&mut self, sent_id: &str, entry: IndexEntry) {
+        if !self.entries.contains_key(sent_id) {
+            self.ids.push(sent_id.to_string());
+            self.entries.insert(sent_id.to_string(), entry);
+        }
+    }
+
+    /// The position of the sentence with the given `sent_id`.
+    pub fn entry(&self, sent_id: &str) -> Option<&IndexEntry> {
+        self.entries.get(sent_id)
+    }
+
+    /// The byte offset of the sentence with the given `sent_id`.
+    pub fn offset(&self, sent_id: &str) -> Option<u64> {
+        self.entry(sent_id).map(|entry| entry.offset)
+    }
+
+    /// Iterate over the `sent_id`s and positions in the order of the file.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &IndexEntry)> {
+        self.ids.iter().map(|id| (id.as_str(), &self.entries[id]))
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Seek to the sentence with the given `sent_id` in `reader`, which must
+    /// contain the indexed document, and parse only that sentence.
+    pub fn get<R: BufRead + Seek>(
+        &self,
+        reader: &mut R,
+        sent_id: &str,
+    ) -> Option<Result<Sentence, DocError>> {
+        let entry = self.entry(sent_id)?;
+        if let Err(e) = reader.seek(SeekFrom::Start(entry.offset)) {
+            return Some(Err(e.into()));
+        }
+
+        let columns = entry.columns.map(|id| self.headers[id].clone());
+        Doc::new(reader)
+            .resume_at(
+                entry.line,
+                entry.offset as usize,
+                DeclaredColumns::new(columns),
+            )
+            .next()
+    }
+
+    /// The default location of the sidecar file for the file at `path`,
+    /// e.g. `train.conllu.idx` for `train.conllu`.
+    pub fn sidecar_path(path: impl AsRef<Path>) -> PathBuf {
+        let mut path = path.as_ref().as_os_str().to_owned();
+        path.push(".idx");
+        PathBuf::from(path)
+    }
+
+    /// Write the index in the sidecar format.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut columns = None;
+        for (sent_id, entry) in self.iter() {
+            if entry.columns != columns {
+                columns = entry.columns;
+                match columns {
+                    Some(id) => writeln!(writer, "# global.columns = {}", self.headers[id])?,
+                    None => writeln!(writer, "# global.columns = {}", Columns::standard())?,
+                }
+            }
+            writeln!(
+                writer,
+                "{}\t{}\t{}",
+                escape_id(sent_id),
+                entry.offset,
+                entry.line
+            )?;
+        }
+        writer.flush()
+    }
+
+    /// Read an index in the sidecar format.
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Index> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid index entry: {line:?}"),
+            )
+        };
+
+        let mut index = Index::default();
+        let mut columns = None;
+        for line in reader.lines() {
+            let line = line?;
+            if let Some(comment) = line.strip_prefix('#') {
+                let header = global_columns(comment.trim_start()).ok_or_else(|| invalid(&line))?;
+                columns = index.columns_id(header);
+                continue;
+            }
+
+            let mut fields = line.split('\t');
+            let (Some(sent_id), Some(offset), Some(line_num), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid(&line));
+            };
+            let entry = IndexEntry {
+                offset: offset.parse().map_err(|_| invalid(&line))?,
+                line: line_num.parse().map_err(|_| invalid(&line))?,
+                columns,
+            };
+            // line numbers start at 1
+            if entry.line == 0 {
+                return Err(invalid(&line));
+            }
+            let sent_id = unescape_id(sent_id).ok_or_else(|| invalid(&line))?;
+            index.insert(&sent_id, entry);
+        }
+
+        Ok(index)
+    }
+
+    /// Save the index to the file at `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+
+    /// Load an index from the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Index> {
+        Index::read(BufReader::new(File::open(path)?))
+    }
+}
+
+/// Escape a `sent_id` for the sidecar format, so that it cannot be mistaken
+/// for a comment or contain a field separator.
+fn escape_id(sent_id: &str) -> String {
+    let mut escaped = String::with_capacity(sent_id.len());
+    for (i, c) in sent_id.char_indices() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '#' if i == 0 => escaped.push_str("\\#"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Undo [escape_id], or `None` for an invalid escape sequence.
+fn unescape_id(escaped: &str) -> Option<String> {
+    let mut sent_id = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            sent_id.push(c);
+            continue;
+        }
+        sent_id.push(match chars.next()? {
+            '\\' => '\\',
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            '#' => '#',
+            _ => return None,
+        });
+    }
+    Some(sent_id)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    const INPUT: &str = "# sent_id = a
+1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_
+
+# global.columns = ID FORM MWE
+# sent_id = b
+1\tkicked\t1:VID
+
+
+# sent_id = c
+1\tbroken
+";
+
+    #[test]
+    fn test_build_and_get() {
+        let index = Index::build(INPUT.as_bytes()).unwrap();
+
+        assert_eq!(
+            index.iter().map(|(id, e)| (id, e.line)).collect::<Vec<_>>(),
+            vec![("a", 1), ("b", 4), ("c", 9)]
+        );
+        assert_eq!(index.offset("b"), INPUT.find("# global").map(|o| o as u64));
+
+        let mut reader = Cursor::new(INPUT);
+        let b = index.get(&mut reader, "b").unwrap().unwrap();
+        assert_eq!(b.tokens[0].extra("MWE"), Some("1:VID"));
+
+        let c = index.get(&mut reader, "c").unwrap().unwrap_err();
+        assert_eq!(c.to_string(), "Parse error in line 10: Missing column: MWE");
+        assert!(index.get(&mut reader, "d").is_none());
+    }
+
+    #[test]
+    fn test_sidecar_roundtrip() {
+        let index = Index::build(INPUT.as_bytes()).unwrap();
+
+        let mut sidecar = vec![];
+        index.write(&mut sidecar).unwrap();
+
+        let b = INPUT.find("# global").unwrap();
+        let c = INPUT.find("# sent_id = c").unwrap();
+        assert_eq!(
+            String::from_utf8(sidecar.clone()).unwrap(),
+            format!("a\t0\t1\nb\t{b}\t4\n# global.columns = ID FORM MWE\nc\t{c}\t9\n")
+        );
+        assert_eq!(Index::read(&sidecar[..]).unwrap(), index);
+        assert_eq!(
+            Index::read(&b"a\t0\t0\n"[..]).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(
+            Index::read(&b"a\\x\t0\t1\n"[..]).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(
+            Index::sidecar_path("data/train.conllu"),
+            PathBuf::from("data/train.conllu.idx")
+        );
+    }
+
+    #[test]
+    fn test_sidecar_escaping() {
+        let input = "# global.columns = ID FORM LEMMA UPOS XPOS FEATS HEAD DEPREL DEPS MISC
+# sent_id = #1
+1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_
+
+# sent_id = a\\b\tc
+1\tWorld\tworld\tNOUN\t_\t_\t0\troot\t_\t_
+";
+        let index = Index::build(input.as_bytes()).unwrap();
+        assert_eq!(index.entry("#1").unwrap().columns, None);
+        assert!(index.entry("a\\b\tc").is_some());
+
+        let mut sidecar = vec![];
+        index.write(&mut sidecar).unwrap();
+
+        let offset = input.find("# sent_id = a").unwrap();
+        assert_eq!(
+            String::from_utf8(sidecar.clone()).unwrap(),
+            format!("\\#1\t0\t1\na\\\\b\\tc\t{offset}\t5\n")
+        );
+        assert_eq!(Index::read(&sidecar[..]).unwrap(), index);
+    }
+}
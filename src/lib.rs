@@ -26,22 +26,69 @@
 //! assert_eq!(token_iter.next().unwrap().form, "buy".to_owned());
 //!
 //! ```
+//!
+//! ## Serde
+//!
+//! With the `serde` feature, [`Sentence`], [`Token`] and the types they contain
+//! implement `Serialize` and `Deserialize` with the following representations:
+//!
+//! - [`TokenID`]s are strings as in the text format, e.g. `"3"`, `"3-4"` or `"3.1"`.
+//! - [`UPOS`] tags are strings like `"NOUN"`.
+//! - [`Features`] are maps from feature names to values, in their original order.
+//! - [`Misc`] is the string of the MISC column, e.g. `"SpaceAfter=No|Gloss=world"`.
+//! - [`Dep`]s are objects with a `head` and a `rel` field.
+//! - [`Metadata`] is a list of the comment lines without the leading `#`.
+//! - `extra_columns` of CoNLL-U Plus files are maps from column names to values
+//!   and are left out if empty.
+//!
+//! Empty fields are `null`.
 
 #![allow(clippy::tabs_in_doc_comments)]
 
 use std::{error::Error, fmt, str::FromStr};
 
+use columns::Columns;
+
+#[cfg(feature = "tokio")]
+pub mod async_io;
+#[cfg(feature = "clap")]
 pub mod cli;
+pub mod columns;
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+pub mod graph;
+pub mod index;
+pub mod metadata;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 pub mod parsers;
+#[cfg(feature = "serde")]
+mod serialization;
 pub mod token;
+pub mod tree;
+pub mod validate;
+pub mod writer;
 
-pub use token::{Dep, Token, TokenID};
+pub use metadata::{MetaEntry, Metadata};
+pub use token::{BorrowedToken, Dep, Features, Misc, Token, TokenID};
 
-pub use parsers::{parse_file, parse_sentence, parse_token};
+pub use parsers::{parse_file, parse_sentence, parse_sentence_borrowed, parse_token};
 
+pub use graph::EnhancedGraph;
+pub use tree::Tree;
+
+pub use writer::Writer;
+
+/// A borrowed morphological feature as a key-value pair,
+/// as yielded by [BorrowedToken::iter_features].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Feature<'a>(pub &'a str, pub &'a str);
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseUposError;
 
 impl fmt::Display for ParseUposError {
@@ -55,6 +102,7 @@ impl Error for ParseUposError {}
 /// The set of Universal POS tags according
 /// to [UD version 2](https://universaldependencies.org/u/pos/index.html).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UPOS {
     ADJ,
     ADP,
@@ -103,12 +151,219 @@ impl FromStr for UPOS {
     }
 }
 
+impl UPOS {
+    /// The tag as it is written in CoNLL-U format.
+    pub fn as_str(&self) -> &'static str {
+        use UPOS::*;
+        match self {
+            ADJ => "ADJ",
+            ADP => "ADP",
+            ADV => "ADV",
+            AUX => "AUX",
+            CCONJ => "CCONJ",
+            DET => "DET",
+            INTJ => "INTJ",
+            NOUN => "NOUN",
+            NUM => "NUM",
+            PART => "PART",
+            PRON => "PRON",
+            PROPN => "PROPN",
+            PUNCT => "PUNCT",
+            SCONJ => "SCONJ",
+            SYM => "SYM",
+            VERB => "VERB",
+            X => "X",
+        }
+    }
+}
+
+impl fmt::Display for UPOS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sentence {
-    pub meta: Vec<String>,
+    pub meta: Metadata,
     pub tokens: Vec<Token>,
 }
 
+impl Sentence {
+    /// A view of the basic dependency tree of the sentence.
+    pub fn tree(&self) -> Tree<'_> {
+        Tree::new(self)
+    }
+
+    /// The enhanced dependency graph of the sentence.
+    pub fn enhanced_graph(&self) -> EnhancedGraph<'_> {
+        EnhancedGraph::new(self)
+    }
+
+    /// Iterate over the syntactic words of the sentence, i.e. the tokens with
+    /// a [TokenID::Single] id, leaving out multiword tokens and empty nodes.
+    ///
+    /// ```rust
+    /// use rs_conllu::{parse_sentence, Token};
+    ///
+    /// let sentence = parse_sentence(
+    ///     "1-2\tvámonos\t_\t_\t_\t_\t_\t_\t_\t_
+    /// 1\tvamos\tir\tVERB\t_\t_\t0\troot\t_\t_
+    /// 2\tnos\tnosotros\tPRON\t_\t_\t1\tobj\t_\t_
+    /// 2.1\tvamos\tir\tVERB\t_\t_\t_\t_\t0:root\t_
+    /// 3-4\tal\t_\t_\t_\t_\t_\t_\t_\t_
+    /// 3\ta\ta\tADP\t_\t_\t5\tcase\t_\t_
+    /// 4\tel\tel\tDET\t_\t_\t5\tdet\t_\t_
+    /// 5\tparque\tparque\tNOUN\t_\t_\t1\tobl\t_\t_
+    /// ",
+    /// )
+    /// .unwrap();
+    ///
+    /// fn forms<'a>(tokens: impl Iterator<Item = &'a Token>) -> Vec<&'a str> {
+    ///     tokens.map(|t| t.form.as_str()).collect()
+    /// }
+    ///
+    /// assert_eq!(forms(sentence.words()), vec!["vamos", "nos", "a", "el", "parque"]);
+    /// assert_eq!(forms(sentence.surface_tokens()), vec!["vámonos", "al", "parque"]);
+    /// assert_eq!(forms(sentence.empty_nodes()), vec!["vamos"]);
+    /// assert_eq!(sentence.multiword_of(4).unwrap().form, "al");
+    /// assert_eq!(sentence.multiword_of(5), None);
+    ///
+    /// let al = sentence.multiword_of(3).unwrap();
+    /// assert_eq!(forms(sentence.parts_of(al.id)), vec!["a", "el"]);
+    /// ```
+    pub fn words(&self) -> impl Iterator<Item = &Token> {
+        self.tokens
+            .iter()
+            .filter(|token| matches!(token.id, TokenID::Single(_)))
+    }
+
+    /// Iterate over the orthographic tokens of the sentence, i.e. the multiword
+    /// tokens and the words that are not part of a multiword token, in the order
+    /// of the text. Empty nodes are left out. See [words](Sentence::words).
+    pub fn surface_tokens(&self) -> impl Iterator<Item = &Token> {
+        let mut covered = 0;
+        self.tokens.iter().filter(move |token| match token.id {
+            TokenID::Range(_, end) => {
+                covered = end;
+                true
+            }
+            TokenID::Single(id) => id > covered,
+            TokenID::Empty(_, _) => false,
+        })
+    }
+
+    /// Iterate over the empty nodes of the sentence, i.e. the tokens with a
+    /// [TokenID::Empty] id. See [words](Sentence::words).
+    pub fn empty_nodes(&self) -> impl Iterator<Item = &Token> {
+        self.tokens
+            .iter()
+            .filter(|token| matches!(token.id, TokenID::Empty(_, _)))
+    }
+
+    /// The multiword token that the word with index `id` is part of, if any.
+    /// See [words](Sentence::words).
+    pub fn multiword_of(&self, id: usize) -> Option<&Token> {
+        self.tokens.iter().find(
+            |token| matches!(token.id, TokenID::Range(start, end) if (start..=end).contains(&id)),
+        )
+    }
+
+    /// Iterate over the words that make up the multiword token with the given
+    /// `range` id. Yields nothing if `range` is not a [TokenID::Range].
+    /// See [words](Sentence::words).
+    pub fn parts_of(&self, range: TokenID) -> impl Iterator<Item = &Token> {
+        self.words().filter(move |token| match (range, token.id) {
+            (TokenID::Range(start, end), TokenID::Single(id)) => (start..=end).contains(&id),
+            _ => false,
+        })
+    }
+
+    /// Format the sentence with the token lines laid out according to `columns`,
+    /// e.g. the columns declared at the beginning of a CoNLL-U Plus file.
+    pub fn display_with<'a>(&'a self, columns: &'a Columns<'a>) -> SentenceDisplay<'a> {
+        SentenceDisplay {
+            sentence: self,
+            columns,
+        }
+    }
+}
+
+/// Writes the sentence in CoNLL-U format: one comment line per entry in
+/// [meta](Sentence::meta), followed by one line per token. Every line is
+/// terminated by a newline, but the blank line separating sentences is not
+/// written; use a [Writer] for complete documents.
+///
+/// If the sentence declares `global.columns`, the token lines follow the
+/// declared columns.
+impl fmt::Display for Sentence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let columns = self
+            .meta
+            .global_columns()
+            .and_then(|header| Columns::parse(header).ok())
+            .unwrap_or_default();
+        write!(f, "{}", self.display_with(&columns))
+    }
+}
+
+/// Formats a [Sentence] with a custom list of columns, see [Sentence::display_with].
+pub struct SentenceDisplay<'a> {
+    sentence: &'a Sentence,
+    columns: &'a Columns<'a>,
+}
+
+impl fmt::Display for SentenceDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.sentence.meta {
+            let entry = entry.to_string();
+            if entry.is_empty() {
+                writeln!(f, "#")?;
+            } else {
+                writeln!(f, "# {entry}")?;
+            }
+        }
+        for token in &self.sentence.tokens {
+            writeln!(f, "{}", token.display_with(self.columns))?;
+        }
+        Ok(())
+    }
+}
+
+/// A zero-copy variant of [Sentence], produced by [parse_sentence_borrowed].
+/// All comments and tokens borrow from the parsed input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorrowedSentence<'a> {
+    pub meta: Vec<&'a str>,
+    pub tokens: Vec<BorrowedToken<'a>>,
+}
+
+impl BorrowedSentence<'_> {
+    /// Convert into an owned [Sentence].
+    pub fn to_sentence(&self) -> Sentence {
+        Sentence {
+            meta: self.meta.iter().map(|m| MetaEntry::parse(m)).collect(),
+            tokens: self.tokens.iter().map(BorrowedToken::to_token).collect(),
+        }
+    }
+}
+
+impl From<BorrowedSentence<'_>> for Sentence {
+    fn from(sentence: BorrowedSentence<'_>) -> Self {
+        sentence.to_sentence()
+    }
+}
+
+impl<'a> IntoIterator for BorrowedSentence<'a> {
+    type Item = BorrowedToken<'a>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.into_iter()
+    }
+}
+
 impl IntoIterator for Sentence {
     type Item = Token;
     type IntoIter = std::vec::IntoIter<Self::Item>;
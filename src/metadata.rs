@@ -0,0 +1,298 @@
+use std::{fmt, slice};
+
+/// A single comment line of a sentence, without the leading `#`.
+///
+/// Entries are written back exactly as they were read, including the spacing
+/// around the `=` of an attribute and trailing whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaEntry {
+    /// An attribute in the form `key = value`, e.g. `sent_id = 1`.
+    Pair {
+        key: String,
+        value: String,
+        /// The comment as it was read, if it is spelled differently from
+        /// `key = value`, e.g. `text=Hello`.
+        raw: Option<String>,
+    },
+    /// A document or paragraph boundary without an id, i.e. `newdoc` or `newpar`.
+    Marker(String),
+    /// Any other, free-form comment.
+    Comment(String),
+}
+
+impl MetaEntry {
+    /// Classify the text of a comment line (with the `#` already stripped).
+    ///
+    /// ```rust
+    /// use rs_conllu::MetaEntry;
+    ///
+    /// assert_eq!(
+    ///     MetaEntry::parse("sent_id = 1"),
+    ///     MetaEntry::Pair { key: "sent_id".to_string(), value: "1".to_string(), raw: None }
+    /// );
+    /// assert_eq!(MetaEntry::parse("text=Hi ").to_string(), "text=Hi ");
+    /// assert_eq!(MetaEntry::parse("newpar"), MetaEntry::Marker("newpar".to_string()));
+    /// assert_eq!(
+    ///     MetaEntry::parse("checked by A = B?"),
+    ///     MetaEntry::Comment("checked by A = B?".to_string())
+    /// );
+    /// ```
+    pub fn parse(comment: &str) -> MetaEntry {
+        let trimmed = comment.trim();
+
+        if trimmed == "newdoc" || trimmed == "newpar" {
+            return MetaEntry::Marker(comment.to_string());
+        }
+
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let key = key.trim();
+            if is_attribute_key(key) {
+                let value = value.trim();
+                let canonical = format!("{key} = {value}");
+                return MetaEntry::Pair {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    raw: (canonical != comment).then(|| comment.to_string()),
+                };
+            }
+        }
+
+        MetaEntry::Comment(comment.to_string())
+    }
+}
+
+/// Attribute keys are single words such as `sent_id` or `global.columns`,
+/// with the exception of the `newdoc id` and `newpar id` attributes.
+fn is_attribute_key(key: &str) -> bool {
+    if key == "newdoc id" || key == "newpar id" {
+        return true;
+    }
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+}
+
+impl fmt::Display for MetaEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaEntry::Pair { raw: Some(raw), .. } => f.write_str(raw),
+            MetaEntry::Pair { key, value, .. } => write!(f, "{key} = {value}"),
+            MetaEntry::Marker(marker) => f.write_str(marker),
+            MetaEntry::Comment(comment) => f.write_str(comment),
+        }
+    }
+}
+
+/// The comment lines preceding the tokens of a [Sentence](crate::Sentence).
+///
+/// Entries are kept in their original order, so writing a sentence reproduces
+/// its comments as they were read. Attributes can be looked up by key, and the
+/// attributes defined by the CoNLL-U specification have dedicated accessors.
+///
+/// ```rust
+/// use rs_conllu::parse_sentence;
+///
+/// let input = "# newdoc id = mf920901-001
+/// ## newpar
+/// ## sent_id = mf920901-001-p1s1A
+/// ## text = Slovenská ústava: pro i proti
+/// ## annotated twice
+/// 1\tSlovenská\tslovenský\tADJ\t_\t_\t2\tamod\t_\t_
+/// ";
+///
+/// let sentence = parse_sentence(input).unwrap();
+/// let meta = &sentence.meta;
+///
+/// assert_eq!(meta.sent_id(), Some("mf920901-001-p1s1A"));
+/// assert_eq!(meta.text(), Some("Slovenská ústava: pro i proti"));
+/// assert_eq!(meta.newdoc(), Some(Some("mf920901-001")));
+/// assert_eq!(meta.newpar(), Some(None));
+/// assert_eq!(meta.comments().collect::<Vec<_>>(), vec!["annotated twice"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    entries: Vec<MetaEntry>,
+}
+
+impl Metadata {
+    pub fn new() -> Self {
+        Metadata::default()
+    }
+
+    /// Append an entry.
+    pub fn push(&mut self, entry: MetaEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Parse the text of a comment line and append it, see [MetaEntry::parse].
+    pub fn push_comment(&mut self, comment: &str) {
+        self.push(MetaEntry::parse(comment));
+    }
+
+    /// Set the value of an attribute. Replaces the value of the first
+    /// attribute with the same key, which is then written as `key = value`,
+    /// or appends a new one.
+    pub fn set(&mut self, key: &str, value: String) {
+        for entry in self.entries.iter_mut() {
+            if let MetaEntry::Pair {
+                key: k,
+                value: v,
+                raw,
+            } = entry
+            {
+                if k == key {
+                    *v = value;
+                    *raw = None;
+                    return;
+                }
+            }
+        }
+        self.entries.push(MetaEntry::Pair {
+            key: key.to_string(),
+            value,
+            raw: None,
+        });
+    }
+
+    /// The value of the first attribute with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// Iterate over all entries in their original order.
+    pub fn iter(&self) -> slice::Iter<'_, MetaEntry> {
+        self.entries.iter()
+    }
+
+    /// Iterate over all `key = value` attributes.
+    pub fn pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().filter_map(|e| match e {
+            MetaEntry::Pair { key, value, .. } => Some((key.as_str(), value.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Iterate over all free-form comments.
+    pub fn comments(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().filter_map(|e| match e {
+            MetaEntry::Comment(comment) => Some(comment.as_str()),
+            _ => None,
+        })
+    }
+
+    /// The `sent_id` attribute.
+    pub fn sent_id(&self) -> Option<&str> {
+        self.get("sent_id")
+    }
+
+    /// The `text` attribute.
+    pub fn text(&self) -> Option<&str> {
+        self.get("text")
+    }
+
+    /// The `global.columns` attribute, which declares the columns of a CoNLL-U Plus file.
+    pub fn global_columns(&self) -> Option<&str> {
+        self.get("global.columns")
+    }
+
+    /// Whether the sentence starts a new document. The inner value is the
+    /// document id, if the boundary is given as `newdoc id = ...`.
+    pub fn newdoc(&self) -> Option<Option<&str>> {
+        self.boundary("newdoc")
+    }
+
+    /// Whether the sentence starts a new paragraph. The inner value is the
+    /// paragraph id, if the boundary is given as `newpar id = ...`.
+    pub fn newpar(&self) -> Option<Option<&str>> {
+        self.boundary("newpar")
+    }
+
+    fn boundary(&self, marker: &str) -> Option<Option<&str>> {
+        self.entries.iter().find_map(|e| match e {
+            MetaEntry::Marker(m) if m.trim() == marker => Some(None),
+            MetaEntry::Pair { key, value, .. } if key.strip_suffix(" id") == Some(marker) => {
+                Some(Some(value.as_str()))
+            }
+            _ => None,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl FromIterator<MetaEntry> for Metadata {
+    fn from_iter<I: IntoIterator<Item = MetaEntry>>(iter: I) -> Self {
+        Metadata {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Metadata {
+    type Item = &'a MetaEntry;
+    type IntoIter = slice::Iter<'a, MetaEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_entry_roundtrip() {
+        for comment in [
+            "sent_id = 1",
+            "newdoc id = doc1",
+            "newdoc",
+            "global.columns = ID FORM LEMMA",
+            "text = a = b",
+            "text=a",
+            "text =  a  ",
+            "newpar ",
+            "This is a comment ",
+        ] {
+            assert_eq!(MetaEntry::parse(comment).to_string(), comment);
+        }
+    }
+
+    #[test]
+    fn test_sentence_roundtrip() {
+        let input = "# sent_id=1
+# text = Hello 
+1\tHello\t_\t_\t_\t_\t_\t_\t_\t_
+";
+        let sentence = crate::parse_sentence(input).unwrap();
+
+        assert_eq!(sentence.meta.sent_id(), Some("1"));
+        assert_eq!(sentence.meta.text(), Some("Hello"));
+        assert_eq!(sentence.to_string(), input);
+        assert_eq!(
+            crate::parse_sentence(&sentence.to_string()).unwrap(),
+            sentence
+        );
+    }
+
+    #[test]
+    fn test_set_replaces_value() {
+        let mut meta = Metadata::new();
+        meta.push_comment("sent_id = 1");
+        meta.push_comment("text = Hello");
+        meta.set("sent_id", "2".to_string());
+        meta.set("translit", "Hallo".to_string());
+
+        assert_eq!(
+            meta.pairs().collect::<Vec<_>>(),
+            vec![("sent_id", "2"), ("text", "Hello"), ("translit", "Hallo")]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Zero-copy reading of memory-mapped files. Requires the `mmap` feature.
+//!
+//! ```rust,no_run
+//! use rs_conllu::mmap::parse_mmap;
+//!
+//! // SAFETY: the corpus is not modified while it is read.
+//! let doc = unsafe { parse_mmap("web-corpus.conllu") }.unwrap();
+//!
+//! for sentence in doc.sentences() {
+//!     let sentence = sentence.unwrap();
+//!     println!("{}", sentence.tokens.len());
+//! }
+//! ```
+
+use std::{fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+use crate::{
+    columns::Columns,
+    parsers::{declared_header, parse_sentence_with_columns, DocError},
+    BorrowedSentence,
+};
+
+/// The UTF-8 byte order mark, which is skipped at the start of the input.
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// A memory-mapped file, see [parse_mmap].
+pub struct MmapDoc {
+    mmap: Mmap,
+}
+
+/// Memory-map the file at `path` for reading its sentences with
+/// [sentences](MmapDoc::sentences).
+///
+/// # Safety
+///
+/// The file must not be modified or truncated while the returned [MmapDoc]
+/// or any sentence borrowed from it is alive, neither by this process nor by
+/// others. The operating system does not prevent changes to a mapped file,
+/// which would change the contents of already validated string slices or
+/// make reading them fault.
+pub unsafe fn parse_mmap(path: impl AsRef<Path>) -> io::Result<MmapDoc> {
+    let file = File::open(path)?;
+    // SAFETY: the mapping is read-only, and the caller guarantees that the
+    // file is not modified while it is mapped.
+    let mmap = unsafe { Mmap::map(&file)? };
+
+    Ok(MmapDoc { mmap })
+}
+
+impl MmapDoc {
+    /// Iterate over the sentences of the file. The sentences borrow their
+    /// comments and fields from the mapping.
+    pub fn sentences(&self) -> MmapSentences<'_> {
+        MmapSentences::new(&self.mmap)
+    }
+}
+
+/// An iterator over the sentences of a byte slice, with the same handling of
+/// errors and line numbers as [Doc](crate::parsers::Doc).
+pub struct MmapSentences<'a> {
+    input: &'a [u8],
+    columns: Option<&'a str>,
+    line_num: usize,
+    offset: usize,
+    sentence_line: usize,
+    sentence_offset: usize,
+}
+
+impl<'a> MmapSentences<'a> {
+    /// Iterate over the sentences of `input`, which is usually a mapped file.
+    pub fn new(input: &'a [u8]) -> Self {
+        MmapSentences {
+            input,
+            columns: None,
+            line_num: 0,
+            offset: 0,
+            sentence_line: 0,
+            sentence_offset: 0,
+        }
+    }
+
+    /// The 1-based line number of the first line of the most recently read sentence.
+    pub fn sentence_line(&self) -> usize {
+        self.sentence_line
+    }
+
+    /// The byte offset of the most recently read sentence within the input.
+    pub fn sentence_offset(&self) -> usize {
+        self.sentence_offset
+    }
+
+    fn parse(&mut self, bytes: &'a [u8]) -> Result<BorrowedSentence<'a>, DocError> {
+        let text = std::str::from_utf8(bytes).map_err(|e| {
+            let before = &bytes[..e.valid_up_to()];
+            DocError::Encoding {
+                line: self.sentence_line + before.iter().filter(|&&b| b == b'\n').count(),
+                offset: self.sentence_offset + e.valid_up_to(),
+                source: e,
+            }
+        })?;
+
+        let columns = match self.columns {
+            Some(header) => Columns::parse(header).unwrap_or_default(),
+            None => Columns::standard(),
+        };
+        let sentence = parse_sentence_with_columns(text, &columns).map_err(|mut e| {
+            e.adjust_line(self.sentence_line);
+            e
+        });
+
+        if let Some(header) = declared_header(text) {
+            self.columns = Some(header);
+        }
+
+        Ok(sentence?)
+    }
+}
+
+/// The length of the blank line at the start of `bytes`, if any. Like in
+/// [Doc](crate::parsers::Doc), a line with only whitespace counts as blank.
+fn blank_line_len(bytes: &[u8]) -> Option<usize> {
+    let len = match bytes.iter().position(|&b| b == b'\n') {
+        Some(i) => i + 1,
+        None => bytes.len(),
+    };
+    let line = std::str::from_utf8(&bytes[..len]).ok()?;
+    (len > 0 && line.trim().is_empty()).then_some(len)
+}
+
+/// The length of the sentence at the start of `bytes`, including the
+/// terminating blank line.
+fn sentence_len(bytes: &[u8]) -> usize {
+    let mut start = 0;
+    while let Some(i) = bytes[start..].iter().position(|&b| b == b'\n') {
+        let end = start + i + 1;
+        if let Some(len) = blank_line_len(&bytes[end..]) {
+            return end + len;
+        }
+        start = end;
+    }
+    bytes.len()
+}
+
+impl<'a> Iterator for MmapSentences<'a> {
+    type Item = Result<BorrowedSentence<'a>, DocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset == 0 && self.input.starts_with(BOM) {
+            self.offset = BOM.len();
+        }
+
+        // skip blank lines before the sentence
+        while let Some(len) = blank_line_len(&self.input[self.offset..]) {
+            self.line_num += 1;
+            self.offset += len;
+        }
+
+        let rest = &self.input[self.offset..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let bytes = &rest[..sentence_len(rest)];
+        self.sentence_line = self.line_num + 1;
+        self.sentence_offset = self.offset;
+        self.line_num += bytes.iter().filter(|&&b| b == b'\n').count();
+        self.offset += bytes.len();
+
+        Some(self.parse(bytes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+    use crate::parsers::Doc;
+
+    fn compare_with_doc(input: &[u8]) {
+        let expected: Vec<_> = Doc::new(input)
+            .map(|s| s.map_err(|e| e.to_string()))
+            .collect();
+        let actual: Vec<_> = MmapSentences::new(input)
+            .map(|s| s.map(|s| s.to_sentence()).map_err(|e| e.to_string()))
+            .collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_same_as_doc() {
+        compare_with_doc(
+            b"# sent_id = 1
+1\tThey\tthey\tPRON\t_\t_\t2\tnsubj\t_\t_
+2\tbuy\tbuy\tVERB\t_\t_\t0\troot\t_\t_
+
+
+# global.columns = ID FORM MWE
+# sent_id = 2
+1\tkicked\t1:VID
+
+1\tbroken
+
+1\tbad\xff\t*
+1\tit\t*",
+        );
+        compare_with_doc(b"");
+        compare_with_doc(b"\n\n1\ta\t*\n");
+        compare_with_doc(
+            b"\xef\xbb\xbf# sent_id = 1\r\n1\ta\t_\t_\t_\t_\t_\t_\t_\t_\r\n\r\n\r\n1\tb\t_\t_\t_\t_\t_\t_\t_\t_\r\n",
+        );
+        compare_with_doc(
+            b" \n1\ta\t_\t_\t_\t_\t_\t_\t_\t_\n\t \r\n1\tb\t_\t_\t_\t_\t_\t_\t_\t_\n  ",
+        );
+    }
+
+    #[test]
+    fn test_parse_mmap() {
+        let path =
+            std::env::temp_dir().join(format!("rs-conllu-mmap-{}.conllu", std::process::id()));
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"# sent_id = 1\n1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_\n\n")
+            .unwrap();
+
+        // SAFETY: the file is only removed after the mapping is dropped.
+        let doc = unsafe { parse_mmap(&path) }.unwrap();
+        let sentences: Vec<_> = doc.sentences().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(sentences[0].meta, vec!["sent_id = 1"]);
+        assert_eq!(sentences[0].tokens[0].form, "Hello");
+
+        drop(doc);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,187 @@
+//! Parse a whole corpus in parallel. Requires the `rayon` feature.
+//!
+//! The input is first split into sentences sequentially, which only needs to
+//! look for blank lines and `global.columns` declarations. The sentences are
+//! then parsed on the rayon thread pool.
+//!
+//! ```rust
+//! use rayon::prelude::*;
+//! use rs_conllu::parallel::par_parse;
+//!
+//! let input = "# sent_id = 1
+//! 1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_
+//!
+//! ## sent_id = 2
+//! 1\tWorld\tworld\tNOUN\t_\t_\t0\troot\t_\t_
+//! ";
+//!
+//! let sentences: Vec<_> = par_parse(input).collect::<Result<_, _>>().unwrap();
+//!
+//! assert_eq!(sentences.len(), 2);
+//! assert_eq!(sentences[1].meta.sent_id(), Some("2"));
+//! ```
+
+use rayon::prelude::*;
+
+use crate::{
+    columns::Columns,
+    parsers::{declared_header, parse_sentence_with_columns, ConlluParseError},
+    Sentence,
+};
+
+/// The text of a single sentence within a larger input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentenceSpan<'a> {
+    /// The lines of the sentence, including the trailing newline.
+    pub text: &'a str,
+    /// The byte offset of the sentence within the input.
+    pub offset: usize,
+    /// The 1-based line number of the first line of the sentence.
+    pub line: usize,
+    /// The most recent valid `global.columns` declaration before the sentence.
+    pub columns: Option<&'a str>,
+}
+
+impl SentenceSpan<'_> {
+    /// Parse the sentence. Line numbers in errors refer to the whole input.
+    pub fn parse(&self) -> Result<Sentence, ConlluParseError> {
+        let columns = self
+            .columns
+            .and_then(|header| Columns::parse(header).ok())
+            .unwrap_or_default();
+
+        parse_sentence_with_columns(self.text, &columns)
+            .map(|s| s.to_sentence())
+            .map_err(|mut e| {
+                e.adjust_line(self.line);
+                e
+            })
+    }
+}
+
+/// An iterator over the sentences of an input, see [split_sentences].
+#[derive(Debug, Clone)]
+pub struct SentenceSpans<'a> {
+    input: &'a str,
+    offset: usize,
+    line: usize,
+    columns: Option<&'a str>,
+}
+
+/// Split `input` into sentences separated by blank lines, without parsing them.
+///
+/// ```rust
+/// use rs_conllu::parallel::split_sentences;
+///
+/// let input = "1\ta\t_\t_\t_\t_\t_\t_\t_\t_\n\n\n1\tb\t_\t_\t_\t_\t_\t_\t_\t_\n";
+///
+/// let spans: Vec<_> = split_sentences(input).collect();
+///
+/// assert_eq!(spans.len(), 2);
+/// assert_eq!(spans[1].offset, 22);
+/// assert_eq!(spans[1].line, 4);
+/// ```
+pub fn split_sentences(input: &str) -> SentenceSpans<'_> {
+    SentenceSpans {
+        input,
+        offset: 0,
+        line: 1,
+        columns: None,
+    }
+}
+
+impl<'a> Iterator for SentenceSpans<'a> {
+    type Item = SentenceSpan<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset == 0 && self.input.starts_with('\u{feff}') {
+            self.offset = '\u{feff}'.len_utf8();
+        }
+        let mut rest = &self.input[self.offset..];
+
+        // skip blank lines before the sentence
+        while let Some(len) = blank_line_len(rest) {
+            self.offset += len;
+            self.line += 1;
+            rest = &rest[len..];
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        let end = rest
+            .match_indices('\n')
+            .map(|(i, _)| i + 1)
+            .find(|&i| blank_line_len(&rest[i..]).is_some())
+            .unwrap_or(rest.len());
+        let span = SentenceSpan {
+            text: &rest[..end],
+            offset: self.offset,
+            line: self.line,
+            columns: self.columns,
+        };
+
+        self.line += span.text.lines().count();
+        if let Some(header) = declared_header(span.text) {
+            self.columns = Some(header);
+        }
+        self.offset += end;
+
+        Some(span)
+    }
+}
+
+/// The length of the blank line at the start of `input`, if any. Like in
+/// [Doc](crate::parsers::Doc), a line with only whitespace counts as blank.
+fn blank_line_len(input: &str) -> Option<usize> {
+    let len = input.find('\n').map_or(input.len(), |i| i + 1);
+    (len > 0 && input[..len].trim().is_empty()).then_some(len)
+}
+
+/// Parse all sentences of `input` in parallel. The iterator is indexed, so
+/// collecting it keeps the sentences in their original order.
+pub fn par_parse(
+    input: &str,
+) -> impl IndexedParallelIterator<Item = Result<Sentence, ConlluParseError>> + '_ {
+    split_sentences(input)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|span| span.parse())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parsers::Doc;
+
+    #[test]
+    fn test_par_parse_matches_doc() {
+        let input = "# sent_id = 1
+1\tThey\tthey\tPRON\t_\t_\t2\tnsubj\t_\t_
+2\tbuy\tbuy\tVERB\t_\t_\t0\troot\t_\t_
+\x20\t
+# global.columns = ID FORM MWE
+# sent_id = 2
+1\tkicked\t1:VID
+2\tit\t*
+
+# sent_id = 3
+1\tdone\t*
+2\tbroken
+";
+        let sequential: Vec<_> = Doc::new(input.as_bytes())
+            .map(|s| s.map_err(|e| e.to_string()))
+            .collect();
+        let parallel: Vec<_> = par_parse(input)
+            .map(|s| s.map_err(|e| e.to_string()))
+            .collect();
+
+        assert_eq!(parallel.len(), 3);
+        assert_eq!(parallel[..2], sequential[..2]);
+        assert_eq!(
+            parallel[2].as_ref().unwrap_err(),
+            "Parse error in line 12: Missing column: MWE"
+        );
+        assert_eq!(parallel[2], sequential[2]);
+    }
+}
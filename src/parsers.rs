@@ -1,19 +1,24 @@
+#[cfg(feature = "compression")]
+use std::path::Path;
 use std::{
-    collections::HashMap,
+    borrow::Cow,
+    fmt,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{self, BufRead, BufReader},
     num::ParseIntError,
-    str::FromStr,
+    ops::Range,
+    str::{FromStr, Utf8Error},
     vec,
 };
 use thiserror::Error;
 
 use crate::{
-    token::{Dep, Token, TokenID},
-    ParseUposError, Sentence, UPOS,
+    columns::{Column, Columns},
+    token::{BorrowedDep, BorrowedToken, Features, Token, TokenID},
+    BorrowedSentence, Feature, Metadata, ParseUposError, Sentence, UPOS,
 };
 
-#[derive(Error, PartialEq, Debug, Eq)]
+#[derive(Error, PartialEq, Debug, Clone, Eq)]
 pub enum ParseIdError {
     #[error("Range must be two integers separated by -")]
     InvalidRange,
@@ -24,7 +29,7 @@ pub enum ParseIdError {
     },
 }
 
-#[derive(Error, Debug, PartialEq, Eq)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum ParseErrorType {
     #[error("Missing field: {0}")]
     MissingField(&'static str),
@@ -37,18 +42,106 @@ pub enum ParseErrorType {
     },
     #[error("Key value pairs must be separated by `=`")]
     KeyValueParseError,
+    #[error("Duplicate feature: {0}")]
+    DuplicateFeature(String),
+    #[error("Missing column: {0}")]
+    MissingColumn(String),
+    #[error("Unexpected field after the last column: {0}")]
+    ExtraField(String),
+    #[error("Invalid global.columns declaration: {0}")]
+    InvalidColumns(String),
 }
 
-#[derive(Error, Debug, PartialEq, Eq)]
+/// An error in a line of a sentence, together with its location.
+///
+/// ```rust
+/// use rs_conllu::parse_sentence;
+///
+/// let input = "1\tcats\tcat\tNOUN\t_\tNumber\t0\troot\t_\t_";
+///
+/// let error = parse_sentence(input).unwrap_err();
+///
+/// assert_eq!(error.line(), 1);
+/// assert_eq!(error.column(), Some(6));
+/// assert_eq!(error.column_name(), Some("FEATS"));
+/// assert_eq!(error.span(), 18..24);
+/// assert_eq!(error.text(), "Number");
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 #[error("Parse error in line {line}: {err}")]
 pub struct ConlluParseError {
-    line: usize,
-    err: ParseErrorType,
+    pub(crate) line: usize,
+    pub(crate) err: ParseErrorType,
+    column: Option<(usize, String)>,
+    span: Range<usize>,
+    line_text: String,
 }
 
 impl ConlluParseError {
-    fn adjust_line(&mut self, offset: usize) {
-        self.line += offset
+    /// Create an error for the bytes `span` of `line_text`, with the line number
+    /// set to 0 until the line is known.
+    pub(crate) fn new(err: ParseErrorType, line_text: &str, span: Range<usize>) -> Self {
+        ConlluParseError {
+            line: 0,
+            err,
+            column: None,
+            span,
+            line_text: line_text.to_string(),
+        }
+    }
+
+    pub(crate) fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    fn in_column(mut self, index: usize, column: Column) -> Self {
+        self.column = Some((index + 1, column.name().to_string()));
+        self
+    }
+
+    /// The 1-based line number of the error. Within a [Doc], this is the line
+    /// number in the document, otherwise the line number within the input.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The kind of error.
+    pub fn kind(&self) -> &ParseErrorType {
+        &self.err
+    }
+
+    /// The 1-based index of the column the error occurred in, if the error is
+    /// in a token line.
+    pub fn column(&self) -> Option<usize> {
+        self.column.as_ref().map(|(index, _)| *index)
+    }
+
+    /// The name of the column the error occurred in, e.g. `FEATS`.
+    pub fn column_name(&self) -> Option<&str> {
+        self.column.as_ref().map(|(_, name)| name.as_str())
+    }
+
+    /// The byte range of the offending text within the line. For missing
+    /// fields, this is the empty range at the end of the line.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The offending text.
+    pub fn text(&self) -> &str {
+        &self.line_text[self.span.clone()]
+    }
+
+    /// The full line the error occurred in.
+    pub fn line_text(&self) -> &str {
+        &self.line_text
+    }
+
+    /// Make the line number relative to a document in which the parsed input
+    /// starts at `first_line`.
+    pub(crate) fn adjust_line(&mut self, first_line: usize) {
+        self.line += first_line - 1
     }
 }
 
@@ -58,6 +151,143 @@ pub fn parse_file(file: File) -> Doc<BufReader<File>> {
     Doc::new(reader)
 }
 
+/// Open and parse the file at `path`. Files compressed with gzip, xz or zstd
+/// are decompressed on the fly, see [decompress].
+///
+/// ```rust,no_run
+/// use rs_conllu::parsers::parse_path;
+///
+/// for sentence in parse_path("en_ewt-ud-train.conllu.gz").unwrap() {
+///     println!("{}", sentence.unwrap().tokens.len());
+/// }
+/// ```
+#[cfg(feature = "compression")]
+pub fn parse_path(path: impl AsRef<Path>) -> io::Result<Doc<Box<dyn BufRead + Send>>> {
+    let reader = BufReader::new(File::open(path)?);
+
+    Ok(Doc::new(decompress(reader)?))
+}
+
+/// Detect the compression of `reader` from its first bytes and wrap it in the
+/// matching decoder. Uncompressed input is returned as is.
+///
+/// ```rust
+/// use std::io::Read;
+///
+/// use rs_conllu::parsers::decompress;
+///
+/// let mut text = String::new();
+/// decompress(&b"1\tHello"[..]).unwrap().read_to_string(&mut text).unwrap();
+///
+/// assert_eq!(text, "1\tHello");
+/// ```
+#[cfg(feature = "compression")]
+pub fn decompress<R: BufRead + Send + 'static>(
+    mut reader: R,
+) -> io::Result<Box<dyn BufRead + Send>> {
+    const GZIP: &[u8] = &[0x1f, 0x8b];
+    const XZ: &[u8] = &[0xfd, b'7', b'z', b'X', b'Z', 0x00];
+    const ZSTD: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+    let magic = reader.fill_buf()?;
+
+    Ok(if magic.starts_with(GZIP) {
+        Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(reader)))
+    } else if magic.starts_with(XZ) {
+        Box::new(BufReader::new(xz2::bufread::XzDecoder::new_multi_decoder(
+            reader,
+        )))
+    } else if magic.starts_with(ZSTD) {
+        Box::new(BufReader::new(zstd::stream::read::Decoder::with_buffer(
+            reader,
+        )?))
+    } else {
+        Box::new(reader)
+    })
+}
+
+/// Options that control which deviations from the CoNLL-U format are
+/// accepted when parsing token lines. By default, parsing is strict and all
+/// options are disabled; [lenient](ParserOptions::lenient) enables all of them.
+///
+/// Every deviation that is accepted is recorded as a [Warning]. Within a [Doc],
+/// warnings are only recorded if enabled with [report_warnings](Doc::report_warnings).
+///
+/// ```rust
+/// use rs_conllu::parsers::{parse_token_with_options, ParserOptions, WarningKind};
+/// use rs_conllu::UPOS;
+///
+/// let options = ParserOptions::new()
+///     .whitespace_separators(true)
+///     .case_insensitive_upos(true);
+///
+/// let (token, warnings) = parse_token_with_options("1 Hello hello intj _ _ 0 root _ _", &options).unwrap();
+///
+/// assert_eq!(token.upos, Some(UPOS::INTJ));
+/// assert_eq!(warnings, vec![WarningKind::WhitespaceSeparators, WarningKind::UposCase]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParserOptions {
+    whitespace_separators: bool,
+    fill_missing_columns: bool,
+    trailing_whitespace: bool,
+    case_insensitive_upos: bool,
+    unknown_upos: bool,
+}
+
+impl ParserOptions {
+    /// Strict parsing, with all options disabled.
+    pub fn new() -> Self {
+        ParserOptions::default()
+    }
+
+    /// Lenient parsing, with all options enabled.
+    pub fn lenient() -> Self {
+        ParserOptions {
+            whitespace_separators: true,
+            fill_missing_columns: true,
+            trailing_whitespace: true,
+            case_insensitive_upos: true,
+            unknown_upos: true,
+        }
+    }
+
+    /// Accept fields separated by runs of spaces instead of tabs. Only applies to
+    /// lines with too few tab-separated fields, so that forms containing spaces
+    /// are still read correctly from tab-separated lines.
+    pub fn whitespace_separators(mut self, allow: bool) -> Self {
+        self.whitespace_separators = allow;
+        self
+    }
+
+    /// Treat missing columns at the end of a line as `_`.
+    pub fn fill_missing_columns(mut self, allow: bool) -> Self {
+        self.fill_missing_columns = allow;
+        self
+    }
+
+    /// Remove whitespace at the end of token lines, which would otherwise be
+    /// part of the last field.
+    pub fn trailing_whitespace(mut self, allow: bool) -> Self {
+        self.trailing_whitespace = allow;
+        self
+    }
+
+    /// Accept UPOS tags regardless of case, e.g. `noun` for [UPOS::NOUN].
+    pub fn case_insensitive_upos(mut self, allow: bool) -> Self {
+        self.case_insensitive_upos = allow;
+        self
+    }
+
+    /// Accept UPOS tags that are not part of UD version 2, such as `CONJ` from
+    /// UD version 1. The tag is treated as `_` and kept in the
+    /// [UnknownUpos](WarningKind::UnknownUpos) warning.
+    pub fn unknown_upos(mut self, allow: bool) -> Self {
+        self.unknown_upos = allow;
+        self
+    }
+}
+
 /// Parse a single line in CoNLL-U format into a [`Token`].
 /// ```
 /// use rs_conllu::{Token, TokenID, UPOS, parse_token};
@@ -74,81 +304,206 @@ pub fn parse_file(file: File) -> Doc<BufReader<File>> {
 ///     head: Some(TokenID::Single(3)),
 ///     deprel: Some("nmod".to_string()),
 ///     deps: None,
-///     misc: None
+///     misc: None,
+///     extra_columns: vec![],
 /// });
 /// ```
 pub fn parse_token(line: &str) -> Result<Token, ParseErrorType> {
-    let mut fields_iter = line.split(|c| c == '\t');
-
-    let id = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("id"))?;
-    let id = parse_id(id)?;
-
-    let form = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("form"))?;
-    let form = String::from(form);
-
-    let lemma = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("lemma"))?;
-    let lemma = placeholder(lemma).map(String::from);
-
-    let upos = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("upos"))?;
-    let upos = placeholder_result(upos, str::parse::<UPOS>)
-        .transpose()
-        .map_err(|e| ParseErrorType::FailedUposParse {
-            source: e,
-            field: upos.to_string(),
-        })?;
-
-    let xpos = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("xpos"))?;
-    let xpos = placeholder(xpos).map(String::from);
-
-    let features = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("features"))?;
-    let features = placeholder_result(features, parse_key_value_pairs).transpose()?;
-
-    let head = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("head"))?;
-    let head = placeholder_result(head, parse_id).transpose()?;
-
-    let deprel = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("deprel"))?;
-    let deprel = placeholder(deprel).map(String::from);
-
-    let deps = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("deps"))?;
-    let deps = placeholder_result(deps, parse_deps).transpose()?;
-
-    let misc = fields_iter
-        .next()
-        .ok_or(ParseErrorType::MissingField("misc"))?;
-    let misc = placeholder(misc).map(String::from);
-
-    Ok(Token {
-        id,
-        form,
-        lemma,
-        upos,
-        xpos,
-        features,
-        head,
-        deprel,
-        deps,
-        misc,
+    parse_token_borrowed(line).map(|t| t.to_token())
+}
+
+/// Parse a single line like [parse_token], accepting the deviations enabled in
+/// `options`. Returns the token together with the deviations that were found.
+pub fn parse_token_with_options(
+    line: &str,
+    options: &ParserOptions,
+) -> Result<(Token, Vec<WarningKind>), ParseErrorType> {
+    let mut warnings = vec![];
+    let token =
+        parse_token_line(line, &Columns::standard(), options, &mut warnings).map_err(|e| e.err)?;
+
+    Ok((token.to_token(), warnings))
+}
+
+/// Parse a single line in CoNLL-U format into a [`BorrowedToken`] without
+/// allocating. All textual fields are slices of `line`.
+pub fn parse_token_borrowed(line: &str) -> Result<BorrowedToken<'_>, ParseErrorType> {
+    parse_token_with_columns(line, &Columns::standard())
+}
+
+/// Parse a single line whose fields are laid out according to `columns`,
+/// e.g. a line of a CoNLL-U Plus file. Standard columns that are not part of
+/// `columns` are left empty and non-standard columns are collected into
+/// [extra_columns](BorrowedToken::extra_columns).
+///
+/// ```rust
+/// use rs_conllu::{columns::Columns, parsers::parse_token_with_columns, UPOS};
+///
+/// let columns = Columns::parse("ID FORM UPOS PARSEME:MWE").unwrap();
+/// let token = parse_token_with_columns("3\tup\tADP\t2", &columns).unwrap();
+///
+/// assert_eq!(token.upos, Some(UPOS::ADP));
+/// assert_eq!(token.lemma, None);
+/// assert_eq!(token.extra("PARSEME:MWE"), Some("2"));
+/// ```
+pub fn parse_token_with_columns<'a>(
+    line: &'a str,
+    columns: &Columns<'a>,
+) -> Result<BorrowedToken<'a>, ParseErrorType> {
+    parse_token_line(line, columns, &ParserOptions::new(), &mut vec![]).map_err(|e| e.err)
+}
+
+/// Parses a token line like [parse_token_with_columns], but reports where in
+/// the line an error occurred. Accepted deviations are added to `warnings`.
+fn parse_token_line<'a>(
+    line: &'a str,
+    columns: &Columns<'a>,
+    options: &ParserOptions,
+    warnings: &mut Vec<WarningKind>,
+) -> Result<BorrowedToken<'a>, ConlluParseError> {
+    let mut fields = line;
+    if options.trailing_whitespace {
+        let trimmed = line.trim_end();
+        if trimmed.len() < line.len() {
+            warnings.push(WarningKind::TrailingWhitespace);
+            fields = trimmed;
+        }
+    }
+
+    let spaces = options.whitespace_separators && {
+        let tabs = fields.split('\t').count();
+        tabs < columns.len() && fields.split_whitespace().count() > tabs
+    };
+    let mut tab_separated = fields.split('\t');
+    let mut space_separated = fields.split_whitespace();
+    let fields_iter: &mut dyn Iterator<Item = &'a str> = if spaces {
+        warnings.push(WarningKind::WhitespaceSeparators);
+        &mut space_separated
+    } else {
+        &mut tab_separated
+    };
+
+    let mut missing = 0;
+    let mut id = None;
+    let mut token = BorrowedToken {
+        id: TokenID::Single(0),
+        form: "_",
+        lemma: None,
+        upos: None,
+        xpos: None,
+        features: None,
+        head: None,
+        deprel: None,
+        deps: None,
+        misc: None,
+        extra_columns: Vec::new(),
+    };
+
+    for (index, &column) in columns.iter().enumerate() {
+        let end = line.len()..line.len();
+        let (field, span) = match fields_iter.next() {
+            Some(field) => (field, span_of(line, field)),
+            None if options.fill_missing_columns => {
+                missing += 1;
+                ("_", end)
+            }
+            None => {
+                return Err(
+                    ConlluParseError::new(column.missing(), line, end).in_column(index, column)
+                );
+            }
+        };
+
+        parse_field(&mut token, &mut id, column, field, options, warnings)
+            .map_err(|e| ConlluParseError::new(e, line, span).in_column(index, column))?;
+    }
+    if missing > 0 {
+        warnings.push(WarningKind::MissingColumns(missing));
+    }
+    if let Some(field) = fields_iter.next() {
+        return Err(ConlluParseError::new(
+            ParseErrorType::ExtraField(field.to_string()),
+            line,
+            span_of(line, field),
+        ));
+    }
+
+    // by convention, LEMMA `_` is the literal lemma of the FORM `_`
+    let lemma = columns.iter().any(|&column| column == Column::Lemma);
+    if lemma
+        && token.form == "_"
+        && token.lemma.is_none()
+        && !matches!(id, Some(TokenID::Range(..)))
+    {
+        token.lemma = Some("_");
+    }
+
+    token.id = id.ok_or_else(|| {
+        ConlluParseError::new(ParseErrorType::MissingField("id"), line, 0..line.len())
+    })?;
+    Ok(token)
+}
+
+fn parse_field<'a>(
+    token: &mut BorrowedToken<'a>,
+    id: &mut Option<TokenID>,
+    column: Column<'a>,
+    field: &'a str,
+    options: &ParserOptions,
+    warnings: &mut Vec<WarningKind>,
+) -> Result<(), ParseErrorType> {
+    match column {
+        Column::Id => *id = Some(parse_id(field)?),
+        Column::Form => token.form = field,
+        Column::Lemma => token.lemma = placeholder(field),
+        Column::Upos => {
+            token.upos = match placeholder(field) {
+                Some(field) => parse_upos(field, options, warnings)?,
+                None => None,
+            }
+        }
+        Column::Xpos => token.xpos = placeholder(field),
+        Column::Feats => token.features = placeholder(field).map(validate_features).transpose()?,
+        Column::Head => token.head = placeholder_result(field, parse_id).transpose()?,
+        Column::Deprel => token.deprel = placeholder(field),
+        Column::Deps => token.deps = placeholder(field).map(validate_deps).transpose()?,
+        Column::Misc => token.misc = placeholder(field),
+        Column::Extra(name) => token.extra_columns.push((name, field)),
+    }
+    Ok(())
+}
+
+/// Parses a UPOS tag, which is `None` if it is unknown and accepted by `options`.
+fn parse_upos(
+    field: &str,
+    options: &ParserOptions,
+    warnings: &mut Vec<WarningKind>,
+) -> Result<Option<UPOS>, ParseErrorType> {
+    if let Ok(upos) = field.parse() {
+        return Ok(Some(upos));
+    }
+    if options.case_insensitive_upos {
+        if let Ok(upos) = field.to_uppercase().parse() {
+            warnings.push(WarningKind::UposCase);
+            return Ok(Some(upos));
+        }
+    }
+    if options.unknown_upos {
+        warnings.push(WarningKind::UnknownUpos(field.to_string()));
+        return Ok(None);
+    }
+    Err(ParseErrorType::FailedUposParse {
+        source: ParseUposError,
+        field: field.to_string(),
     })
 }
 
+/// The byte range of `part`, which must be a slice of `line`, within `line`.
+fn span_of(line: &str, part: &str) -> Range<usize> {
+    let start = part.as_ptr() as usize - line.as_ptr() as usize;
+    start..start + part.len()
+}
+
 fn parse_int(input: &str) -> Result<usize, ParseIdError> {
     let parsed = usize::from_str(input).map_err(|e| ParseIdError::FailedIntParsing {
         input: input.to_string(),
@@ -157,26 +512,21 @@ fn parse_int(input: &str) -> Result<usize, ParseIdError> {
     Ok(parsed)
 }
 
-fn parse_id(field: &str) -> Result<TokenID, ParseIdError> {
-    let sep = ['-', '.'].iter().find(|s| field.contains(**s));
+pub(crate) fn parse_id(field: &str) -> Result<TokenID, ParseIdError> {
+    let sep = ['-', '.'].into_iter().find(|s| field.contains(*s));
 
     if let Some(sep) = sep {
-        let ids: Vec<&str> = field.split(*sep).collect();
-
-        let ids = ids
-            .iter()
-            .map(|s| parse_int(s))
-            .collect::<Result<Vec<usize>, _>>();
-
-        let ids = ids?;
+        let (first, second) = field.split_once(sep).ok_or(ParseIdError::InvalidRange)?;
+        let first = parse_int(first)?;
 
-        if ids.len() != 2 {
+        if second.contains(sep) {
             return Err(ParseIdError::InvalidRange);
         }
+        let second = parse_int(second)?;
 
         return match sep {
-            '-' => Ok(TokenID::Range(ids[0], ids[1])),
-            '.' => Ok(TokenID::Empty(ids[0], ids[1])),
+            '-' => Ok(TokenID::Range(first, second)),
+            '.' => Ok(TokenID::Empty(first, second)),
             _ => panic!(),
         };
     }
@@ -184,39 +534,61 @@ fn parse_id(field: &str) -> Result<TokenID, ParseIdError> {
     Ok(TokenID::Single(parse_int(field)?))
 }
 
-fn parse_key_value_pairs(field: &str) -> Result<HashMap<String, String>, ParseErrorType> {
-    let kv_pairs: Vec<&str> = field.split('|').collect();
-    let features: Result<Vec<(&str, &str)>, _> = kv_pairs
-        .iter()
-        .map(|p| p.split_once('=').ok_or(ParseErrorType::KeyValueParseError))
-        .collect();
+/// Checks that the features column consists of `|`-separated key-value pairs
+/// with unique keys.
+fn validate_features(field: &str) -> Result<&str, ParseErrorType> {
+    for (i, pair) in field.split('|').enumerate() {
+        let (key, _) = pair
+            .split_once('=')
+            .ok_or(ParseErrorType::KeyValueParseError)?;
+        if split_features(field).take(i).any(|Feature(k, _)| k == key) {
+            return Err(ParseErrorType::DuplicateFeature(key.to_string()));
+        }
+    }
+    Ok(field)
+}
 
-    let features: HashMap<String, String> = features?
-        .iter()
-        .map(|t| (t.0.to_owned(), t.1.to_owned()))
-        .collect();
+/// Parse a features column into [Features].
+pub(crate) fn parse_key_value_pairs(field: &str) -> Result<Features, ParseErrorType> {
+    let field = validate_features(field)?;
 
-    Ok(features)
+    Ok(split_features(field)
+        .map(|Feature(k, v)| (k.to_string(), v.to_string()))
+        .collect())
 }
 
-fn parse_deps(field: &str) -> Result<Vec<Dep>, ParseErrorType> {
-    let kv_pairs: Vec<&str> = field.split('|').collect();
-    let deps: Result<Vec<(&str, &str)>, _> = kv_pairs
-        .iter()
-        .map(|p| p.split_once(':').ok_or(ParseErrorType::KeyValueParseError))
-        .collect();
+/// Checks that the deps column consists of `|`-separated `head:rel` pairs.
+fn validate_deps(field: &str) -> Result<&str, ParseErrorType> {
+    parse_deps(field).try_for_each(|dep| dep.map(drop))?;
+    Ok(field)
+}
 
-    let deps: Result<Vec<Dep>, ParseIdError> = deps?
-        .iter()
-        .map(|t| {
-            Ok(Dep {
-                head: parse_id(t.0)?,
-                rel: String::from(t.1),
-            })
-        })
-        .collect();
+/// Split a features column that passed [validate_features] into its entries.
+pub(crate) fn split_features(field: &str) -> impl Iterator<Item = Feature<'_>> {
+    field.split('|').filter_map(|p| {
+        let (key, value) = p.split_once('=')?;
+        Some(Feature(key, value))
+    })
+}
 
-    Ok(deps?)
+/// Split a deps column that passed [validate_deps] into its entries.
+pub(crate) fn split_deps(field: &str) -> impl Iterator<Item = BorrowedDep<'_>> {
+    parse_deps(field).filter_map(Result::ok)
+}
+
+/// Split a deps column into its entries, parsing the head of each entry.
+/// Shared by [validate_deps] and [split_deps], so that both accept the same
+/// entries.
+fn parse_deps(field: &str) -> impl Iterator<Item = Result<BorrowedDep<'_>, ParseErrorType>> {
+    field.split('|').map(|pair| {
+        let (head, rel) = pair
+            .split_once(':')
+            .ok_or(ParseErrorType::KeyValueParseError)?;
+        Ok(BorrowedDep {
+            head: parse_id(head)?,
+            rel,
+        })
+    })
 }
 
 fn placeholder(field: &str) -> Option<&str> {
@@ -238,20 +610,364 @@ where
 
 /// Parses a single sentence in ConLL-U format.
 pub fn parse_sentence(input: &str) -> Result<Sentence, ConlluParseError> {
+    parse_sentence_borrowed(input).map(|s| s.to_sentence())
+}
+
+/// Parses a single sentence in ConLL-U format into a [`BorrowedSentence`],
+/// whose comments and tokens are slices of `input`.
+///
+/// ```rust
+/// use rs_conllu::parse_sentence_borrowed;
+///
+/// let input = "# sent_id = 1
+/// 1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_
+/// ".to_string();
+///
+/// let sentence = parse_sentence_borrowed(&input).unwrap();
+///
+/// assert_eq!(sentence.meta, vec!["sent_id = 1"]);
+/// assert_eq!(sentence.tokens[0].lemma, Some("hello"));
+/// ```
+pub fn parse_sentence_borrowed(input: &str) -> Result<BorrowedSentence<'_>, ConlluParseError> {
+    parse_sentence_with_columns(input, &Columns::standard())
+}
+
+/// Parses a single sentence whose token lines are laid out according to `columns`.
+/// A `global.columns` comment within the sentence takes precedence over `columns`.
+///
+/// ```rust
+/// use rs_conllu::parsers::parse_sentence_with_columns;
+/// use rs_conllu::columns::Columns;
+///
+/// let input = "# global.columns = ID FORM PARSEME:MWE
+/// 1\tkicked\t1:VID
+/// 2\tthe\t1
+/// 3\tbucket\t1
+/// ";
+///
+/// let sentence = parse_sentence_with_columns(input, &Columns::standard()).unwrap();
+///
+/// assert_eq!(sentence.tokens[2].form, "bucket");
+/// assert_eq!(sentence.tokens[2].extra("PARSEME:MWE"), Some("1"));
+/// ```
+pub fn parse_sentence_with_columns<'a>(
+    input: &'a str,
+    columns: &Columns<'a>,
+) -> Result<BorrowedSentence<'a>, ConlluParseError> {
+    parse_sentence_lines(input, columns, &ParserOptions::new(), &mut vec![])
+}
+
+/// Parses a single sentence like [parse_sentence], accepting the deviations
+/// enabled in `options`. Returns the sentence together with the deviations
+/// that were found, with the line numbers within `input`.
+///
+/// ```rust
+/// use rs_conllu::parsers::{parse_sentence_with_options, ParserOptions, WarningKind};
+/// use rs_conllu::UPOS;
+///
+/// let input = "# sent_id = 1
+/// 1\tThey\tthey\tPRON
+/// 2\tbuy\tbuy\tVERB\tVBP\t_\t0\troot\t_\t_
+/// 3\tand\tand\tCONJ\tCC\t_\t4\tcc\t_\t_
+/// ";
+///
+/// let (sentence, warnings) = parse_sentence_with_options(input, &ParserOptions::lenient()).unwrap();
+///
+/// assert_eq!(sentence.tokens[0].head, None);
+/// assert_eq!(sentence.tokens[1].upos, Some(UPOS::VERB));
+/// assert_eq!(sentence.tokens[2].upos, None);
+///
+/// let warnings: Vec<_> = warnings.into_iter().map(|w| (w.line, w.kind)).collect();
+/// assert_eq!(
+///     warnings,
+///     vec![
+///         (2, WarningKind::MissingColumns(6)),
+///         (4, WarningKind::UnknownUpos("CONJ".to_string())),
+///     ]
+/// );
+/// ```
+pub fn parse_sentence_with_options(
+    input: &str,
+    options: &ParserOptions,
+) -> Result<(Sentence, Vec<Warning>), ConlluParseError> {
+    let mut warnings = vec![];
+    let sentence = parse_sentence_lines(input, &Columns::standard(), options, &mut warnings)?;
+
+    Ok((sentence.to_sentence(), warnings))
+}
+
+/// Parses the lines of a sentence, adding accepted deviations to `warnings`.
+fn parse_sentence_lines<'a>(
+    input: &'a str,
+    columns: &Columns<'a>,
+    options: &ParserOptions,
+    warnings: &mut Vec<Warning>,
+) -> Result<BorrowedSentence<'a>, ConlluParseError> {
+    let mut columns = columns.clone();
     let mut meta = vec![];
     let mut tokens = vec![];
     for (i, line) in input.lines().enumerate() {
         if let Some(comment) = line.strip_prefix('#') {
             let comment = comment.trim_start();
-            meta.push(comment.to_string());
+            if let Some(header) = comment_columns(line, comment) {
+                columns = header.map_err(|e| e.at_line(i + 1))?;
+            }
+            meta.push(comment);
             continue;
         }
-        if !line.is_empty() {
-            let token = parse_token(line).map_err(|e| ConlluParseError { err: e, line: i })?;
+        if !line.trim().is_empty() {
+            let mut kinds = vec![];
+            let token = parse_token_line(line, &columns, options, &mut kinds);
+            warnings.extend(kinds.into_iter().map(|kind| Warning { line: i + 1, kind }));
+            tokens.push(token.map_err(|e| e.at_line(i + 1))?);
+        }
+    }
+    Ok(BorrowedSentence { meta, tokens })
+}
+
+/// A sentence parsed by [parse_sentence_recovering], which may contain lines
+/// that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialSentence {
+    pub meta: Metadata,
+    /// The tokens of the sentence in their original order, with an error in
+    /// place of every token line that could not be parsed.
+    pub tokens: Vec<Result<Token, ConlluParseError>>,
+    /// Errors in comment lines, i.e. invalid `global.columns` declarations.
+    pub comment_errors: Vec<ConlluParseError>,
+    pub(crate) lines: SentenceLines,
+}
+
+/// The line numbers of the comment and token lines of a sentence, in the
+/// order of its metadata entries and tokens.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SentenceLines {
+    pub(crate) meta: Vec<usize>,
+    pub(crate) tokens: Vec<usize>,
+}
+
+impl SentenceLines {
+    /// The lines of a sentence starting in line `first_line`, with all its
+    /// comment lines first, followed by one line per token.
+    pub(crate) fn contiguous(first_line: usize, meta: usize, tokens: usize) -> SentenceLines {
+        SentenceLines {
+            meta: (first_line..first_line + meta).collect(),
+            tokens: (first_line + meta..first_line + meta + tokens).collect(),
+        }
+    }
+}
+
+impl PartialSentence {
+    /// Whether all lines of the sentence could be parsed.
+    pub fn is_complete(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Iterate over all errors in the order of their lines.
+    pub fn errors(&self) -> impl Iterator<Item = &ConlluParseError> {
+        let mut errors: Vec<&ConlluParseError> = self
+            .comment_errors
+            .iter()
+            .chain(self.tokens.iter().filter_map(|t| t.as_ref().err()))
+            .collect();
+        errors.sort_by_key(|e| e.line);
+        errors.into_iter()
+    }
+
+    /// Convert into a [Sentence] with the tokens that could not be parsed left out.
+    pub fn into_sentence(self) -> Sentence {
+        Sentence {
+            meta: self.meta,
+            tokens: self.tokens.into_iter().filter_map(Result::ok).collect(),
+        }
+    }
+
+    /// Convert into a [Sentence] if all lines could be parsed, or return all errors.
+    pub fn into_result(self) -> Result<Sentence, Vec<ConlluParseError>> {
+        if self.is_complete() {
+            Ok(self.into_sentence())
+        } else {
+            Err(self.errors().cloned().collect())
+        }
+    }
+
+    fn adjust_lines(&mut self, first_line: usize) {
+        self.comment_errors
+            .iter_mut()
+            .chain(self.tokens.iter_mut().filter_map(|t| t.as_mut().err()))
+            .for_each(|e| e.adjust_line(first_line));
+        self.lines
+            .meta
+            .iter_mut()
+            .chain(self.lines.tokens.iter_mut())
+            .for_each(|line| *line += first_line - 1);
+    }
+}
+
+/// Parses a single sentence like [parse_sentence], but continues after lines
+/// that cannot be parsed, so that all errors of the sentence are reported at once.
+///
+/// ```rust
+/// use rs_conllu::parsers::parse_sentence_recovering;
+///
+/// let input = "# sent_id = 1
+/// 1\tThey\tthey\tPRON\tPRP\t_\t2\tnsubj\t_\t_
+/// 2\tbuy\tbuy\tVRB\tVBP\t_\t0\troot\t_\t_
+/// 3\tbooks\tbook\tNOUN\tNNS\t_\t2
+/// ";
+///
+/// let sentence = parse_sentence_recovering(input);
+///
+/// assert_eq!(sentence.meta.sent_id(), Some("1"));
+/// assert!(sentence.tokens[0].is_ok());
+/// assert_eq!(sentence.errors().map(|e| e.line()).collect::<Vec<_>>(), vec![3, 4]);
+/// assert_eq!(sentence.into_sentence().tokens.len(), 1);
+/// ```
+pub fn parse_sentence_recovering(input: &str) -> PartialSentence {
+    parse_sentence_recovering_with_columns(input, &Columns::standard())
+}
+
+/// Parses a single sentence like [parse_sentence_with_columns], but continues
+/// after lines that cannot be parsed. See [parse_sentence_recovering].
+pub fn parse_sentence_recovering_with_columns(input: &str, columns: &Columns) -> PartialSentence {
+    parse_sentence_lines_recovering(input, columns, &ParserOptions::new(), &mut vec![])
+}
+
+/// Parses the lines of a sentence in recovering mode, adding accepted
+/// deviations to `warnings`.
+fn parse_sentence_lines_recovering(
+    input: &str,
+    columns: &Columns,
+    options: &ParserOptions,
+    warnings: &mut Vec<Warning>,
+) -> PartialSentence {
+    let mut columns = columns.clone();
+    let mut meta = Metadata::new();
+    let mut tokens = vec![];
+    let mut comment_errors = vec![];
+    let mut lines = SentenceLines::default();
+    for (i, line) in input.lines().enumerate() {
+        if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim_start();
+            if let Some(header) = comment_columns(line, comment) {
+                match header {
+                    Ok(header) => columns = header,
+                    Err(e) => comment_errors.push(e.at_line(i + 1)),
+                }
+            }
+            meta.push_comment(comment);
+            lines.meta.push(i + 1);
+            continue;
+        }
+        if !line.trim().is_empty() {
+            let mut kinds = vec![];
+            let token = parse_token_line(line, &columns, options, &mut kinds)
+                .map(|t| t.to_token())
+                .map_err(|e| e.at_line(i + 1));
+            warnings.extend(kinds.into_iter().map(|kind| Warning { line: i + 1, kind }));
             tokens.push(token);
+            lines.tokens.push(i + 1);
         }
     }
-    Ok(Sentence { meta, tokens })
+    PartialSentence {
+        meta,
+        tokens,
+        comment_errors,
+        lines,
+    }
+}
+
+/// The columns declared by `comment`, the text of the comment `line`, if it
+/// is a `global.columns` declaration.
+pub(crate) fn comment_columns<'a>(
+    line: &str,
+    comment: &'a str,
+) -> Option<Result<Columns<'a>, ConlluParseError>> {
+    let header = global_columns(comment)?;
+    Some(Columns::parse(header).map_err(|e| ConlluParseError::new(e, line, span_of(line, header))))
+}
+
+/// The last valid `global.columns` declaration among the comment lines of a
+/// sentence, which applies to the following sentences of a document even if
+/// the sentence itself cannot be parsed.
+pub(crate) fn declared_header(input: &str) -> Option<&str> {
+    input
+        .lines()
+        .filter_map(|line| line.strip_prefix('#'))
+        .filter_map(|comment| global_columns(comment.trim_start()))
+        .rfind(|header| Columns::parse(header).is_ok())
+}
+
+/// The value of a `global.columns = ...` comment.
+pub(crate) fn global_columns(comment: &str) -> Option<&str> {
+    let (key, value) = comment.split_once('=')?;
+    (key.trim() == "global.columns").then(|| value.trim())
+}
+
+/// An irregularity in the input that does not prevent parsing, either in the
+/// layout of a [Doc] or a deviation accepted because of [ParserOptions].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The line the irregularity was found in.
+    pub line: usize,
+    pub kind: WarningKind,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Warning in line {}: {}", self.line, self.kind)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// The document starts with a UTF-8 byte order mark, which was removed.
+    #[error("Byte order mark at the start of the document")]
+    ByteOrderMark,
+    /// Lines end with `\r\n`. Only reported for the first such line.
+    #[error("Lines end with CRLF")]
+    CrlfLineEndings,
+    /// A blank line that does not end a sentence, which was skipped.
+    #[error("Extra blank line")]
+    ExtraBlankLine,
+    /// A line that consists only of whitespace, which was treated as a blank line.
+    #[error("Line consists only of whitespace")]
+    WhitespaceLine,
+    /// Whitespace at the end of a token line, which was removed.
+    #[error("Trailing whitespace")]
+    TrailingWhitespace,
+    /// The fields of a token line are separated by spaces.
+    #[error("Fields separated by spaces instead of tabs")]
+    WhitespaceSeparators,
+    /// The given number of columns at the end of a token line are missing
+    /// and were treated as `_`.
+    #[error("{0} missing column(s) treated as _")]
+    MissingColumns(usize),
+    /// A UPOS tag is not written in uppercase.
+    #[error("UPOS tag not in uppercase")]
+    UposCase,
+    /// A UPOS tag is not part of UD version 2 and was treated as `_`.
+    #[error("Unknown UPOS tag {0} treated as _")]
+    UnknownUpos(String),
+}
+
+/// An error that occurs while reading sentences from a [Doc].
+#[derive(Error, Debug)]
+pub enum DocError {
+    /// Reading from the underlying reader failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// A line is not valid UTF-8. `offset` is the byte offset of the first
+    /// invalid byte from the start of the input.
+    #[error("Invalid UTF-8 in line {line} at byte offset {offset}")]
+    Encoding {
+        line: usize,
+        offset: usize,
+        source: Utf8Error,
+    },
+    /// A sentence could not be parsed.
+    #[error(transparent)]
+    Parse(#[from] ConlluParseError),
 }
 
 /// A `Doc` is a wrapper around a type that implements [BufRead] and produces
@@ -263,7 +979,7 @@ pub fn parse_sentence(input: &str) -> Result<Sentence, ConlluParseError> {
 ///
 /// ```rust
 /// use std::io::BufReader;
-/// use rs_conllu::{Sentence, Token, TokenID};
+/// use rs_conllu::{Metadata, Sentence, Token, TokenID};
 /// use rs_conllu::parsers::Doc;
 ///
 /// let conllu = "1\tSue\t_\t_\t_\t_\t_\t_\t_\t_
@@ -275,78 +991,469 @@ pub fn parse_sentence(input: &str) -> Result<Sentence, ConlluParseError> {
 ///
 /// let mut doc = Doc::new(reader);
 ///
-/// assert_eq!(doc.next(), Some(Ok(Sentence {
-///     meta: vec![],
+/// assert_eq!(doc.next().unwrap().unwrap(), Sentence {
+///     meta: Metadata::new(),
 ///     tokens: vec![
 ///         Token::builder(TokenID::Single(1), "Sue".to_string()).build(),
 ///         Token::builder(TokenID::Single(2), "likes".to_string()).build(),
 ///         Token::builder(TokenID::Single(3), "coffee".to_string()).build(),
 ///     ]
-/// })));
+/// });
 /// ```
 ///
+/// Iteration yields a [DocError] for sentences that cannot be read or parsed.
+/// Parse and encoding errors only affect the sentence they occur in and iteration
+/// continues with the next sentence, while an I/O error ends the iteration.
+/// With [lossy](Doc::lossy) decoding, invalid UTF-8 is replaced by
+/// `U+FFFD REPLACEMENT CHARACTER` instead of being reported.
+///
+/// Documents in CoNLL-U Plus format are supported: once a sentence declares
+/// its columns in a `global.columns` comment, this and all following sentences
+/// are parsed according to the declared columns.
 pub struct Doc<T: BufRead> {
     reader: T,
-    line_num: usize,
+    pub(crate) state: DocState,
+    line_buffer: Vec<u8>,
 }
 
 impl<T: BufRead> Doc<T> {
     pub fn new(reader: T) -> Self {
         Doc {
             reader,
-            line_num: 0,
+            state: DocState::default(),
+            line_buffer: Vec::new(),
+        }
+    }
+
+    /// Enable or disable lossy decoding of invalid UTF-8.
+    ///
+    /// ```rust
+    /// use rs_conllu::parsers::Doc;
+    ///
+    /// let conllu = b"1\tSue\xff\t_\t_\t_\t_\t_\t_\t_\t_\n";
+    ///
+    /// let mut doc = Doc::new(&conllu[..]).lossy(true);
+    ///
+    /// assert_eq!(doc.next().unwrap().unwrap().tokens[0].form, "Sue\u{FFFD}");
+    /// ```
+    pub fn lossy(mut self, lossy: bool) -> Self {
+        self.state.lossy = lossy;
+        self
+    }
+
+    /// Set the options for parsing the sentences of the document, which are
+    /// strict by default.
+    ///
+    /// ```rust
+    /// use rs_conllu::parsers::{Doc, ParserOptions};
+    ///
+    /// let conllu = "1 Sue Sue PROPN";
+    ///
+    /// assert!(Doc::new(conllu.as_bytes()).next().unwrap().is_err());
+    ///
+    /// let mut doc = Doc::new(conllu.as_bytes())
+    ///     .options(ParserOptions::lenient())
+    ///     .report_warnings(true);
+    ///
+    /// assert_eq!(doc.next().unwrap().unwrap().tokens[0].lemma.as_deref(), Some("Sue"));
+    /// assert_eq!(doc.warnings().len(), 2);
+    /// ```
+    pub fn options(mut self, options: ParserOptions) -> Self {
+        self.state.options = options;
+        self
+    }
+
+    /// Enable or disable recording [Warning]s about irregular but accepted input,
+    /// which can be retrieved with [warnings](Doc::warnings) or
+    /// [take_warnings](Doc::take_warnings).
+    ///
+    /// ```rust
+    /// use rs_conllu::parsers::{Doc, WarningKind};
+    ///
+    /// let conllu = "\u{feff}1\tSue\t_\t_\t_\t_\t_\t_\t_\t_\r\n\r\n\r\n1\tlikes\t_\t_\t_\t_\t_\t_\t_\t_\r\n";
+    ///
+    /// let mut doc = Doc::new(conllu.as_bytes()).report_warnings(true);
+    ///
+    /// assert_eq!(doc.next().unwrap().unwrap().tokens[0].form, "Sue");
+    /// assert_eq!(doc.next().unwrap().unwrap().tokens[0].form, "likes");
+    /// assert!(doc.next().is_none());
+    ///
+    /// let warnings: Vec<_> = doc.warnings().iter().map(|w| (w.line, w.kind.clone())).collect();
+    /// assert_eq!(warnings, vec![
+    ///     (1, WarningKind::ByteOrderMark),
+    ///     (1, WarningKind::CrlfLineEndings),
+    ///     (3, WarningKind::ExtraBlankLine),
+    /// ]);
+    /// ```
+    pub fn report_warnings(mut self, report: bool) -> Self {
+        self.state.report_warnings = report;
+        self
+    }
+
+    /// The warnings recorded so far, if enabled with
+    /// [report_warnings](Doc::report_warnings).
+    pub fn warnings(&self) -> &[Warning] {
+        &self.state.warnings
+    }
+
+    /// Remove and return the warnings recorded so far, e.g. after each sentence.
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.state.warnings)
+    }
+
+    /// Continue a document at the given position, e.g. after seeking to the
+    /// start of a sentence, so that errors report the correct lines and offsets.
+    pub(crate) fn resume_at(
+        mut self,
+        line: usize,
+        offset: usize,
+        columns: DeclaredColumns,
+    ) -> Self {
+        self.state.line_num = line - 1;
+        self.state.offset = offset;
+        self.state.columns = columns;
+        self
+    }
+
+    /// The 1-based line number of the first line of the most recently read sentence.
+    ///
+    /// ```rust
+    /// use rs_conllu::parsers::Doc;
+    ///
+    /// let conllu = "# sent_id = 1
+    /// 1\tHello\t_\t_\t_\t_\t_\t_\t_\t_
+    ///
+    /// ## sent_id = 2
+    /// 1\tWorld\t_\t_\t_\t_\t_\t_\t_\t_
+    /// ";
+    ///
+    /// let mut doc = Doc::new(conllu.as_bytes());
+    /// doc.next();
+    /// doc.next();
+    ///
+    /// assert_eq!(doc.sentence_line(), 4);
+    /// assert_eq!(doc.sentence_offset(), 39);
+    /// ```
+    pub fn sentence_line(&self) -> usize {
+        self.state.sentence_line()
+    }
+
+    /// The byte offset of the most recently read sentence within the input.
+    pub fn sentence_offset(&self) -> usize {
+        self.state.sentence_offset()
+    }
+
+    /// Read the lines of the next sentence into a buffer.
+    pub(crate) fn read_sentence(&mut self) -> Option<Result<String, DocError>> {
+        while !self.state.is_finished() {
+            self.line_buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.line_buffer) {
+                Ok(0) => return self.state.finish(),
+                Ok(_) => {
+                    if let Some(sentence) = self.state.push_line(&self.line_buffer) {
+                        return Some(sentence);
+                    }
+                }
+                Err(e) => return Some(Err(self.state.fail(e))),
+            }
         }
+        None
+    }
+
+    /// Read the next sentence in recovering mode, see [parse_sentence_recovering].
+    /// Only I/O and encoding errors are returned as [DocError]; parse errors are
+    /// part of the [PartialSentence].
+    ///
+    /// ```rust
+    /// use rs_conllu::parsers::Doc;
+    ///
+    /// let conllu = "1\tSue\t_\t_\t_\t_\t_\t_\t_\t_
+    /// 2\tlikes\t_\t_\t_\t_\t_
+    /// 3\tcoffee\t_\t_\t_\t_\t_\t_\t_\t_
+    ///
+    /// 1\tSo\t_\t_\t_\t_\t_\t_\t_\t_
+    /// 2\tdo\t_\t_\t_\t_\t_\t_\t_\tX=Y
+    /// ".as_bytes();
+    ///
+    /// let mut doc = Doc::new(conllu);
+    ///
+    /// let first = doc.next_recovering().unwrap().unwrap();
+    /// assert_eq!(first.errors().next().unwrap().line(), 2);
+    ///
+    /// let second = doc.next_recovering().unwrap().unwrap();
+    /// assert!(second.is_complete());
+    /// ```
+    pub fn next_recovering(&mut self) -> Option<Result<PartialSentence, DocError>> {
+        let buffer = match self.read_sentence()? {
+            Ok(buffer) => buffer,
+            Err(e) => return Some(Err(e)),
+        };
+
+        Some(Ok(self.state.parse_recovering(&buffer)))
     }
 }
 
 impl<T: BufRead> Iterator for Doc<T> {
-    type Item = Result<Sentence, ConlluParseError>;
+    type Item = Result<Sentence, DocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut buffer = String::new();
-        let mut num_lines_in_buffer = 0;
+        let buffer = match self.read_sentence()? {
+            Ok(buffer) => buffer,
+            Err(e) => return Some(Err(e)),
+        };
 
-        // try to read a line from the buffer
-        // if we read 0 bytes, we are at EOF and stop the iteration
-        // by returning None
-        let mut bytes = self.reader.read_line(&mut buffer).unwrap();
-        self.line_num += 1;
-        num_lines_in_buffer += 1;
-        if bytes == 0 {
+        Some(self.state.parse(&buffer))
+    }
+}
+
+/// The reading state of a document, independent of how its lines are read:
+/// splits the lines into sentences, keeps track of line numbers, offsets,
+/// declared columns and warnings, and parses the sentences. Shared by [Doc]
+/// and the asynchronous reader, which feed it one line at a time.
+#[derive(Debug, Default)]
+pub(crate) struct DocState {
+    pub(crate) columns: DeclaredColumns,
+    line_num: usize,
+    sentence_line: usize,
+    offset: usize,
+    sentence_offset: usize,
+    pub(crate) lossy: bool,
+    pub(crate) options: ParserOptions,
+    pub(crate) report_warnings: bool,
+    pub(crate) warnings: Vec<Warning>,
+    crlf: bool,
+    finished: bool,
+    /// The lines of the sentence read so far.
+    buffer: String,
+    /// The first invalid UTF-8 in the sentence read so far.
+    encoding_error: Option<DocError>,
+}
+
+impl DocState {
+    pub(crate) fn sentence_line(&self) -> usize {
+        self.sentence_line
+    }
+
+    pub(crate) fn sentence_offset(&self) -> usize {
+        self.sentence_offset
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Process the next line of the document, including its line ending.
+    /// Returns the lines of a sentence once the blank line ending it is read;
+    /// a line with only whitespace counts as blank.
+    pub(crate) fn push_line(&mut self, line: &[u8]) -> Option<Result<String, DocError>> {
+        let start = self.buffer.len();
+        if start == 0 {
+            self.sentence_line = self.line_num + 1;
+            self.sentence_offset = self.offset;
+        }
+
+        self.decode_line(line);
+        if !self.buffer[start..].trim().is_empty() {
+            return None;
+        }
+        if &self.buffer[start..] != "\n" {
+            self.buffer.truncate(start);
+            self.buffer.push('\n');
+            self.warn(WarningKind::WhitespaceLine);
+        }
+        if start == 0 {
+            // blank lines before a sentence are skipped
+            self.buffer.clear();
+            self.warn(WarningKind::ExtraBlankLine);
             return None;
         }
+        Some(self.take_sentence())
+    }
 
-        // fill the buffer until we are at a sentence break
-        // or at the end of the file
-        // while !buffer.ends_with("\n\n") && bytes != 0 {
-        //     bytes = self.reader.read_line(&mut buffer).unwrap();
-        // }
-        loop {
-            bytes = self.reader.read_line(&mut buffer).unwrap();
-            self.line_num += 1;
-            num_lines_in_buffer += 1;
-            if buffer.ends_with("\n\n") {
-                break;
+    /// Process the end of the document, returning the last sentence if it is
+    /// not followed by a blank line.
+    pub(crate) fn finish(&mut self) -> Option<Result<String, DocError>> {
+        self.finished = true;
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some(self.take_sentence())
+    }
+
+    /// Process an I/O error, which ends the document.
+    pub(crate) fn fail(&mut self, error: io::Error) -> DocError {
+        self.finished = true;
+        DocError::Io(error)
+    }
+
+    fn take_sentence(&mut self) -> Result<String, DocError> {
+        let buffer = std::mem::take(&mut self.buffer);
+        match self.encoding_error.take() {
+            Some(e) => Err(e),
+            None => Ok(buffer),
+        }
+    }
+
+    /// Append a line to the buffer, with a byte order mark at the start of the
+    /// document removed and a CRLF line ending replaced by `\n`. Invalid UTF-8
+    /// is always replaced, but the first such occurrence of the sentence is
+    /// stored as its error unless decoding is lossy.
+    fn decode_line(&mut self, bytes: &[u8]) {
+        self.line_num += 1;
+
+        let line = match std::str::from_utf8(bytes) {
+            Ok(line) => Cow::Borrowed(line),
+            Err(e) => {
+                if !self.lossy && self.encoding_error.is_none() {
+                    self.encoding_error = Some(DocError::Encoding {
+                        line: self.line_num,
+                        offset: self.offset + e.valid_up_to(),
+                        source: e,
+                    });
+                }
+                String::from_utf8_lossy(bytes)
             }
-            // at EOF, the buffer terminates with a single newline.
-            // To treat them equally with other sentences finishing in
-            // a double newline, add one here.
-            if bytes == 0 {
-                buffer.push('\n');
-                break;
+        };
+
+        let mut line = line.as_ref();
+        let mut warnings = vec![];
+        if self.line_num == 1 {
+            if let Some(stripped) = line.strip_prefix('\u{feff}') {
+                line = stripped;
+                warnings.push(WarningKind::ByteOrderMark);
+            }
+        }
+        match line.strip_suffix("\r\n") {
+            Some(stripped) => {
+                self.buffer.push_str(stripped);
+                self.buffer.push('\n');
+                if !self.crlf {
+                    self.crlf = true;
+                    warnings.push(WarningKind::CrlfLineEndings);
+                }
             }
+            None => self
+                .buffer
+                .push_str(line.strip_suffix('\r').unwrap_or(line)),
         }
-        Some(parse_sentence(&buffer).map_err(|mut e| {
-            e.adjust_line(self.line_num - num_lines_in_buffer + 1);
-            e
-        }))
+
+        for kind in warnings {
+            self.warn(kind);
+        }
+        self.offset += bytes.len();
+    }
+
+    /// Parse the lines of the most recently read sentence.
+    pub(crate) fn parse(&mut self, buffer: &str) -> Result<Sentence, DocError> {
+        let mut warnings = vec![];
+        let sentence =
+            self.columns
+                .parse_sentence(buffer, self.sentence_line, &self.options, &mut warnings);
+        self.add_warnings(warnings);
+
+        Ok(sentence?)
+    }
+
+    /// Parse the lines of the most recently read sentence in recovering mode.
+    pub(crate) fn parse_recovering(&mut self, buffer: &str) -> PartialSentence {
+        let mut warnings = vec![];
+        let mut sentence = parse_sentence_lines_recovering(
+            buffer,
+            &self.columns.columns(),
+            &self.options,
+            &mut warnings,
+        );
+        self.columns.update_from(buffer);
+        sentence.adjust_lines(self.sentence_line);
+        self.add_warnings(warnings);
+
+        sentence
+    }
+
+    /// Record the warnings of the most recently read sentence.
+    fn add_warnings(&mut self, warnings: Vec<Warning>) {
+        if self.report_warnings {
+            let first_line = self.sentence_line;
+            self.warnings.extend(warnings.into_iter().map(|w| Warning {
+                line: w.line + first_line - 1,
+                ..w
+            }));
+        }
+    }
+
+    fn warn(&mut self, kind: WarningKind) {
+        if self.report_warnings {
+            self.warnings.push(Warning {
+                line: self.line_num,
+                kind,
+            });
+        }
+    }
+}
+
+/// The columns declared by the most recent valid `global.columns` comment of a
+/// document, which apply to all following sentences.
+#[derive(Debug, Default)]
+pub(crate) struct DeclaredColumns(Option<String>);
+
+impl DeclaredColumns {
+    pub(crate) fn new(header: Option<String>) -> Self {
+        DeclaredColumns(header)
+    }
+
+    /// The most recent valid `global.columns` declaration.
+    pub(crate) fn header(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    pub(crate) fn columns(&self) -> Columns<'_> {
+        match &self.0 {
+            Some(header) => Columns::parse(header).unwrap_or_default(),
+            None => Columns::standard(),
+        }
+    }
+
+    /// Remember the columns declared by a sentence for the following ones.
+    pub(crate) fn update(&mut self, meta: &Metadata) {
+        if let Some(header) = meta.global_columns() {
+            if Columns::parse(header).is_ok() {
+                self.0 = Some(header.to_string());
+            }
+        }
+    }
+
+    /// Remember the columns declared in the comment lines of a sentence of the
+    /// document for the following ones, see [declared_header].
+    pub(crate) fn update_from(&mut self, buffer: &str) {
+        if let Some(header) = declared_header(buffer) {
+            self.0 = Some(header.to_string());
+        }
+    }
+
+    /// Parse the lines of a sentence of the document, whose first line is
+    /// `first_line`. The lines of the `warnings` are relative to the sentence.
+    pub(crate) fn parse_sentence(
+        &mut self,
+        buffer: &str,
+        first_line: usize,
+        options: &ParserOptions,
+        warnings: &mut Vec<Warning>,
+    ) -> Result<Sentence, ConlluParseError> {
+        let columns = self.columns();
+        let sentence = parse_sentence_lines(buffer, &columns, options, warnings)
+            .map(|s| s.to_sentence())
+            .map_err(|mut e| {
+                e.adjust_line(first_line);
+                e
+            });
+        self.update_from(buffer);
+
+        sentence
     }
 }
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
-
     use crate::{Token, UPOS};
 
     use super::*;
@@ -366,11 +1473,45 @@ mod test {
         assert_eq!(parse_id("5.6"), Ok(TokenID::Empty(5, 6)));
     }
 
+    #[test]
+    fn invalid_id_range() {
+        assert_eq!(parse_id("5-6-7"), Err(ParseIdError::InvalidRange));
+    }
+
+    #[test]
+    fn test_borrowed_token_parse() {
+        let line = "5\tdem\tder\tDET\tART\tCase=Dat|Definite=Def\t6\tdet\t6:det|4.1:det\t_";
+
+        let token = parse_token_borrowed(line).unwrap();
+
+        assert_eq!(token.form, "dem");
+        assert_eq!(token.misc, None);
+        assert_eq!(
+            token.iter_features().collect::<Vec<_>>(),
+            vec![Feature("Case", "Dat"), Feature("Definite", "Def")]
+        );
+        assert_eq!(
+            token.iter_deps().collect::<Vec<_>>(),
+            vec![
+                BorrowedDep {
+                    head: TokenID::Single(6),
+                    rel: "det"
+                },
+                BorrowedDep {
+                    head: TokenID::Empty(4, 1),
+                    rel: "det"
+                }
+            ]
+        );
+        assert_eq!(token.to_token(), parse_token(line).unwrap());
+    }
+
     #[test]
     fn test_token_parse() {
-        let line = "2	Ein	ein	DET	DT	Case=Nom|Definite=Ind|Gender=Masc|Number=Sing|Person=3	3	det	_	_";
+        let line =
+            "2	Ein	ein	DET	DT	Case=Nom|Definite=Ind|Gender=Masc|Number=Sing|Person=3	3	det	_	_";
 
-        let features = HashMap::from([
+        let features = Features::from([
             ("Case".to_string(), "Nom".to_string()),
             ("Definite".to_string(), "Ind".to_string()),
             ("Gender".to_string(), "Masc".to_string()),
@@ -389,8 +1530,382 @@ mod test {
             deprel: Some("det".to_string()),
             deps: None,
             misc: None,
+            extra_columns: vec![],
         };
 
         assert_eq!(token, parse_token(line).unwrap());
     }
+
+    #[test]
+    fn test_doc_encoding_error() {
+        let input = b"1\ta\t_\t_\t_\t_\t_\t_\t_\t_\n\n1\tb\xc3\t_\t_\t_\t_\t_\t_\t_\t_\n\n1\tc\t_\t_\t_\t_\t_\t_\t_\t_\n";
+
+        let mut doc = Doc::new(&input[..]);
+
+        assert!(doc.next().unwrap().is_ok());
+        match doc.next().unwrap() {
+            Err(DocError::Encoding { line, offset, .. }) => {
+                assert_eq!(line, 3);
+                assert_eq!(offset, 24);
+            }
+            other => panic!("expected encoding error, got {other:?}"),
+        }
+        assert_eq!(doc.next().unwrap().unwrap().tokens[0].form, "c");
+        assert!(doc.next().is_none());
+    }
+
+    #[test]
+    fn test_doc_io_error_ends_iteration() {
+        struct FailingReader;
+
+        impl io::Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+            }
+        }
+
+        let mut doc = Doc::new(BufReader::new(FailingReader));
+
+        assert!(matches!(doc.next(), Some(Err(DocError::Io(_)))));
+        assert!(doc.next().is_none());
+    }
+
+    #[test]
+    fn test_doc_conllu_plus() {
+        let input =
+            "# global.columns = ID FORM LEMMA UPOS XPOS FEATS HEAD DEPREL DEPS MISC PARSEME:MWE
+# sent_id = 1
+1\tTake\ttake\tVERB\t_\t_\t0\troot\t_\t_\t1:LVC.full
+2\ta\ta\tDET\t_\t_\t3\tdet\t_\t_\t*
+3\tbreak\tbreak\tNOUN\t_\t_\t1\tobj\t_\t_\t1
+
+# sent_id = 2
+1\tStop\tstop\tVERB\t_\t_\t0\troot\t_\t_\t*
+";
+
+        let sentences = Doc::new(input.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            sentences[0].tokens[0].extra("PARSEME:MWE"),
+            Some("1:LVC.full")
+        );
+        assert_eq!(sentences[1].tokens[0].extra("PARSEME:MWE"), Some("*"));
+        assert_eq!(sentences[1].tokens[0].upos, Some(UPOS::VERB));
+    }
+
+    #[test]
+    fn test_missing_extra_column() {
+        let columns = Columns::parse("ID FORM PARSEME:MWE").unwrap();
+
+        assert_eq!(
+            parse_token_with_columns("1\tTake", &columns),
+            Err(ParseErrorType::MissingColumn("PARSEME:MWE".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_token_misc_parse() {
+        let line = "3\tlevel\tlevel\tNOUN\t_\t_\t0\troot\t_\tSpaceAfter=No|Gloss=floor|Bare";
+
+        let misc = parse_token(line).unwrap().misc.unwrap();
+
+        assert!(!misc.space_after());
+        assert_eq!(misc.gloss(), Some("floor"));
+        assert_eq!(misc.get("Bare"), None);
+        assert!(misc.contains("Bare"));
+        assert_eq!(misc.to_string(), "SpaceAfter=No|Gloss=floor|Bare");
+    }
+
+    #[test]
+    fn test_duplicate_feature() {
+        let line = "1\tdog\tdog\tNOUN\t_\tNumber=Sing|Case=Nom|Number=Plur\t0\troot\t_\t_";
+
+        assert_eq!(
+            parse_token(line),
+            Err(ParseErrorType::DuplicateFeature("Number".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_features_canonical_order() {
+        let features = parse_key_value_pairs("Number=Sing|abbr=Yes|PronType=Rel,Int").unwrap();
+
+        assert!(!features.is_sorted());
+        assert_eq!(
+            features.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            vec!["Number", "abbr", "PronType"]
+        );
+        assert_eq!(
+            features.to_string(),
+            "abbr=Yes|Number=Sing|PronType=Int,Rel"
+        );
+        assert_eq!(
+            features,
+            parse_key_value_pairs("abbr=Yes|Number=Sing|PronType=Rel,Int").unwrap()
+        );
+        assert!(parse_key_value_pairs(&features.to_string())
+            .unwrap()
+            .is_sorted());
+    }
+
+    #[test]
+    fn test_columns_declared_by_invalid_sentence() {
+        let input = "# global.columns = ID FORM MWE\n1\tx\n\n1\ty\tz\n";
+
+        let mut doc = Doc::new(input.as_bytes());
+        assert!(doc.next().unwrap().is_err());
+        assert_eq!(
+            doc.next().unwrap().unwrap().tokens[0].extra("MWE"),
+            Some("z")
+        );
+
+        let mut doc = Doc::new(input.as_bytes());
+        assert!(!doc.next_recovering().unwrap().unwrap().is_complete());
+        assert!(doc.next_recovering().unwrap().unwrap().is_complete());
+    }
+
+    #[test]
+    fn test_extra_field() {
+        let line = "1\ta\t_\t_\t_\t_\t_\t_\t_\t_\tEXTRA";
+
+        assert_eq!(
+            parse_token(line),
+            Err(ParseErrorType::ExtraField("EXTRA".to_string()))
+        );
+        let error = parse_sentence(line).unwrap_err();
+        assert_eq!(error.text(), "EXTRA");
+        assert_eq!(error.column(), None);
+    }
+
+    #[test]
+    fn test_recovering_sentence() {
+        let input = "# global.columns = ID FORM
+# sent_id = a
+1\tdog
+x\tbarks
+3\tloudly
+";
+
+        let sentence = parse_sentence_recovering(input);
+
+        assert!(sentence.comment_errors.is_empty());
+        assert_eq!(sentence.tokens.len(), 3);
+        assert!(sentence.tokens[0].is_ok());
+        let error = sentence.tokens[1].as_ref().unwrap_err();
+        assert_eq!(error.line(), 4);
+        assert!(matches!(error.kind(), ParseErrorType::FailedIdParse(_)));
+        assert!(sentence.tokens[2].is_ok());
+        assert_eq!(sentence.clone().into_sentence().tokens.len(), 2);
+        assert_eq!(sentence.into_result().unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_recovering_invalid_columns() {
+        let input = "# global.columns = FORM LEMMA
+1\tdog\tdog\tNOUN\t_\t_\t0\troot\t_\t_
+2\tbarks\tbark\tVERB\t_\t_\t1
+";
+
+        let sentence = parse_sentence_recovering(input);
+
+        assert_eq!(sentence.meta.len(), 1);
+        assert_eq!(
+            sentence.errors().map(|e| e.line()).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(sentence.tokens[0].is_ok());
+    }
+
+    #[test]
+    fn test_doc_recovering_lines() {
+        let input = "# sent_id = 1
+1\ta\t_\t_\t_\t_\t_\t_\t_\t_
+
+# sent_id = 2
+1\tb\t_\t_\t_\tFoo\t_\t_\t_\t_
+2\tc\t_\t_\t_\t_\t_\t_\t_\t_
+3\td\t_\t_\t_\t_\t_
+";
+        let mut doc = Doc::new(input.as_bytes());
+
+        assert!(doc.next_recovering().unwrap().unwrap().is_complete());
+        let second = doc.next_recovering().unwrap().unwrap();
+        assert_eq!(doc.sentence_line(), 4);
+        assert_eq!(
+            second.errors().map(|e| e.line()).collect::<Vec<_>>(),
+            vec![5, 7]
+        );
+        assert!(doc.next_recovering().is_none());
+    }
+
+    #[test]
+    fn test_doc_sentence_boundaries() {
+        let input = "\u{feff}# sent_id = 1\r\n1\ta\t_\t_\t_\t_\t_\t_\t_\t_\r\n\r\n\r\n\n# sent_id = 2\r\n1\tb\t_\t_\t_\t_\t_\t_\t_\t_\r\n\r\n\n";
+        let mut doc = Doc::new(input.as_bytes()).report_warnings(true);
+
+        let first = doc.next().unwrap().unwrap();
+        assert_eq!(first.meta.sent_id(), Some("1"));
+        assert_eq!(first.tokens[0].misc, None);
+        assert_eq!(
+            doc.take_warnings(),
+            vec![
+                Warning {
+                    line: 1,
+                    kind: WarningKind::ByteOrderMark
+                },
+                Warning {
+                    line: 1,
+                    kind: WarningKind::CrlfLineEndings
+                },
+            ]
+        );
+
+        let second = doc.next().unwrap().unwrap();
+        assert_eq!(doc.sentence_line(), 6);
+        assert_eq!(second.tokens[0].form, "b");
+        assert!(doc.next().is_none());
+        assert_eq!(
+            doc.warnings().iter().map(|w| w.line).collect::<Vec<_>>(),
+            vec![4, 5, 9]
+        );
+        assert!(doc
+            .warnings()
+            .iter()
+            .all(|w| w.kind == WarningKind::ExtraBlankLine));
+
+        assert_eq!(Doc::new("\n\n".as_bytes()).count(), 0);
+        let mut doc = Doc::new(input.as_bytes());
+        assert_eq!(doc.by_ref().count(), 2);
+        assert!(doc.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_doc_whitespace_lines() {
+        let input = " \n# sent_id = 1\n1\ta\t_\t_\t_\t_\t_\t_\t_\t_\n\t \r\n1\tb\t_\t_\t_\t_\t_\t_\t_\t_\n  ";
+        let mut doc = Doc::new(input.as_bytes()).report_warnings(true);
+
+        let first = doc.next().unwrap().unwrap();
+        assert_eq!(first.meta.sent_id(), Some("1"));
+        assert_eq!(first.tokens.len(), 1);
+        let second = doc.next().unwrap().unwrap();
+        assert_eq!(doc.sentence_line(), 5);
+        assert_eq!(second.tokens[0].form, "b");
+        assert!(doc.next().is_none());
+
+        assert_eq!(
+            doc.warnings()
+                .iter()
+                .map(|w| (w.line, w.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, WarningKind::WhitespaceLine),
+                (1, WarningKind::ExtraBlankLine),
+                (4, WarningKind::CrlfLineEndings),
+                (4, WarningKind::WhitespaceLine),
+                (6, WarningKind::WhitespaceLine),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parser_options() {
+        let line = "1\tNew York\tNew York\tpropn\t_\t_\t0\troot\t_\tSpaceAfter=No  ";
+
+        assert!(parse_token_with_options(line, &ParserOptions::new()).is_err());
+
+        let (token, warnings) = parse_token_with_options(line, &ParserOptions::lenient()).unwrap();
+        assert_eq!(token.form, "New York");
+        assert_eq!(token.upos, Some(UPOS::PROPN));
+        assert_eq!(token.misc.unwrap().to_string(), "SpaceAfter=No");
+        assert_eq!(
+            warnings,
+            vec![WarningKind::TrailingWhitespace, WarningKind::UposCase]
+        );
+
+        let options = ParserOptions::new().unknown_upos(true);
+        let error = parse_token_with_options("1\tand\tand\tconj", &options).unwrap_err();
+        assert_eq!(error, ParseErrorType::MissingField("xpos"));
+        let (token, warnings) =
+            parse_token_with_options("1\tand\tand\tconj\t_\t_\t_\t_\t_\t_", &options).unwrap();
+        assert_eq!(token.upos, None);
+        assert_eq!(warnings, vec![WarningKind::UnknownUpos("conj".to_string())]);
+
+        let options = ParserOptions::new().case_insensitive_upos(true);
+        assert!(parse_token_with_options("1\tand\tand\tconj\t_\t_\t_\t_\t_\t_", &options).is_err());
+    }
+
+    #[test]
+    fn test_lenient_error_location() {
+        let input = "1  cats  cat  noun  _  Number  0  root";
+        let error = Doc::new(input.as_bytes())
+            .options(ParserOptions::lenient())
+            .next()
+            .unwrap()
+            .unwrap_err();
+
+        let DocError::Parse(error) = error else {
+            panic!("expected a parse error, got {error}");
+        };
+        assert_eq!(error.line(), 1);
+        assert_eq!(error.column(), Some(6));
+        assert_eq!(error.text(), "Number");
+    }
+
+    #[test]
+    fn test_error_location() {
+        let input = "# global.columns = ID FORM UPOS
+1\tcafé\tNOUN
+2\tau\tADP
+3\tlait\tNONE
+";
+        let error = parse_sentence(input).unwrap_err();
+
+        assert_eq!(error.line(), 4);
+        assert_eq!(error.column(), Some(3));
+        assert_eq!(error.column_name(), Some("UPOS"));
+        assert_eq!(error.span(), 7..11);
+        assert_eq!(error.text(), "NONE");
+        assert_eq!(error.line_text(), "3\tlait\tNONE");
+
+        let error = parse_sentence("1\tcafé\tcafé\tNOUN").unwrap_err();
+
+        assert_eq!(error.kind(), &ParseErrorType::MissingField("xpos"));
+        assert_eq!(error.column(), Some(5));
+        assert_eq!(error.span(), 18..18);
+        assert_eq!(error.text(), "");
+
+        let error = parse_sentence("# global.columns = FORM LEMMA\n").unwrap_err();
+
+        assert_eq!(error.column(), None);
+        assert_eq!(error.text(), "FORM LEMMA");
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_decompress() {
+        use std::io::{Read, Write};
+
+        let input = "# sent_id = 1\n1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_\n\n";
+
+        let mut gzip = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        gzip.write_all(input.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+
+        let mut xz = xz2::write::XzEncoder::new(vec![], 6);
+        xz.write_all(input.as_bytes()).unwrap();
+        let xz = xz.finish().unwrap();
+
+        let zstd = zstd::encode_all(input.as_bytes(), 0).unwrap();
+
+        for compressed in [gzip, xz, zstd, input.as_bytes().to_vec()] {
+            let mut text = String::new();
+            decompress(io::Cursor::new(compressed))
+                .unwrap()
+                .read_to_string(&mut text)
+                .unwrap();
+            assert_eq!(text, input);
+        }
+    }
 }
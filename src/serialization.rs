@@ -0,0 +1,245 @@
+//! Serde implementations for the types that are not derived, see the
+//! crate documentation for the representations.
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    metadata::{MetaEntry, Metadata},
+    token::{Features, Misc, TokenID},
+    UPOS,
+};
+
+/// Serialize types with a [fmt::Display] and [std::str::FromStr] implementation
+/// as strings.
+macro_rules! string_serde {
+    ($type:ty, $expecting:literal) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(FromStrVisitor($expecting, PhantomData))
+            }
+        }
+    };
+}
+
+string_serde!(TokenID, "a token id like `3`, `3-4` or `3.1`");
+string_serde!(Misc, "the contents of a MISC column");
+
+struct FromStrVisitor<T>(&'static str, PhantomData<T>);
+
+impl<T> Visitor<'_> for FromStrVisitor<T>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+impl UPOS {
+    /// Deserialize an optional UPOS tag, treating tags that are not part of
+    /// UD version 2 as `None` instead of failing. For use with
+    /// `#[serde(deserialize_with = "UPOS::deserialize_lenient")]`.
+    ///
+    /// ```rust
+    /// use rs_conllu::UPOS;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Word {
+    ///     #[serde(deserialize_with = "UPOS::deserialize_lenient")]
+    ///     upos: Option<UPOS>,
+    /// }
+    ///
+    /// let word: Word = serde_json::from_str(r#"{"upos": "CONJ"}"#).unwrap();
+    /// assert_eq!(word.upos, None);
+    /// ```
+    pub fn deserialize_lenient<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<UPOS>, D::Error> {
+        let tag = Option::<String>::deserialize(deserializer)?;
+        Ok(tag.and_then(|tag| tag.parse().ok()))
+    }
+}
+
+impl Serialize for Features {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Features {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pairs = pairs::deserialize(deserializer)?;
+        for (i, (key, _)) in pairs.iter().enumerate() {
+            if pairs[..i].iter().any(|(k, _)| k == key) {
+                return Err(de::Error::custom(format!("duplicate feature {key}")));
+            }
+        }
+        Ok(pairs.into_iter().collect())
+    }
+}
+
+impl Serialize for Metadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for entry in self {
+            seq.serialize_element(&entry.to_string())?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Metadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MetadataVisitor;
+
+        impl<'de> Visitor<'de> for MetadataVisitor {
+            type Value = Metadata;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of comments")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Metadata, A::Error> {
+                let mut meta = Metadata::new();
+                while let Some(comment) = seq.next_element::<String>()? {
+                    meta.push(MetaEntry::parse(&comment));
+                }
+                Ok(meta)
+            }
+        }
+
+        deserializer.deserialize_seq(MetadataVisitor)
+    }
+}
+
+/// A list of string pairs as a map that keeps the order of its entries.
+pub(crate) mod pairs {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        pairs: &[(String, String)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(pairs.len()))?;
+        for (key, value) in pairs {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(String, String)>, D::Error> {
+        struct PairsVisitor;
+
+        impl<'de> Visitor<'de> for PairsVisitor {
+            type Value = Vec<(String, String)>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of strings")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(pair) = map.next_entry()? {
+                    pairs.push(pair);
+                }
+                Ok(pairs)
+            }
+        }
+
+        deserializer.deserialize_map(PairsVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{parse_sentence, Sentence, Token, TokenID};
+
+    use super::*;
+
+    #[test]
+    fn test_token_representation() {
+        let input = "# global.columns = ID FORM LEMMA UPOS XPOS FEATS HEAD DEPREL DEPS MISC MWE
+3\tsell\tsell\tVERB\tVBP\tTense=Pres|Number=Plur\t2\tconj\t2:conj|3.1:nsubj\tSpaceAfter=No\t*
+";
+        let sentence = parse_sentence(input).unwrap();
+
+        let value = serde_json::to_value(&sentence.tokens[0]).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "id": "3",
+                "form": "sell",
+                "lemma": "sell",
+                "upos": "VERB",
+                "xpos": "VBP",
+                "features": {"Tense": "Pres", "Number": "Plur"},
+                "head": "2",
+                "deprel": "conj",
+                "deps": [{"head": "2", "rel": "conj"}, {"head": "3.1", "rel": "nsubj"}],
+                "misc": "SpaceAfter=No",
+                "extra_columns": {"MWE": "*"},
+            })
+        );
+        let features = serde_json::to_string(&sentence.tokens[0].features).unwrap();
+        assert_eq!(features, r#"{"Tense":"Pres","Number":"Plur"}"#);
+    }
+
+    #[test]
+    fn test_sentence_roundtrip() {
+        let input = "# newdoc
+# sent_id = 1
+# a comment
+1-2\tdu\t_\t_\t_\t_\t_\t_\t_\t_
+1\tde\tde\tADP\t_\t_\t3\tcase\t_\t_
+2\tle\tle\tDET\t_\tDefinite=Def|Gender=Masc\t3\tdet\t_\t_
+3\tmonde\tmonde\tNOUN\t_\t_\t0\troot\t0:root\tGloss=world|Checked
+3.1\tsaw\tsee\tVERB\t_\t_\t_\t_\t3:conj\t_
+";
+        let sentence = parse_sentence(input).unwrap();
+
+        let json = serde_json::to_string(&sentence).unwrap();
+        let deserialized: Sentence = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, sentence);
+        assert_eq!(deserialized.to_string(), input);
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert!(serde_json::from_str::<TokenID>(r#""3-""#).is_err());
+        assert!(serde_json::from_str::<Features>(r#"{"A": "1", "A": "2"}"#).is_err());
+        assert!(serde_json::from_str::<UPOS>(r#""CONJ""#).is_err());
+
+        let token: Token = serde_json::from_value(json!({
+            "id": "1", "form": "a", "lemma": null, "upos": null, "xpos": null,
+            "features": null, "head": null, "deprel": null, "deps": null, "misc": null,
+        }))
+        .unwrap();
+        assert!(token.extra_columns.is_empty());
+    }
+}
@@ -1,8 +1,14 @@
-use std::collections::HashMap;
+use std::{cmp::Ordering, convert::Infallible, fmt, str::FromStr};
 
-use crate::UPOS;
+use crate::{
+    columns::{Column, Columns},
+    parsers::{
+        parse_id, parse_key_value_pairs, split_deps, split_features, ParseErrorType, ParseIdError,
+    },
+    Feature, UPOS,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TokenID {
     /// The standard, single index.
     Single(usize),
@@ -15,7 +21,211 @@ pub enum TokenID {
     Empty(usize, usize),
 }
 
-type Features = HashMap<String, String>;
+impl FromStr for TokenID {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_id(s)
+    }
+}
+
+impl fmt::Display for TokenID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenID::Single(id) => write!(f, "{id}"),
+            TokenID::Range(start, end) => write!(f, "{start}-{end}"),
+            TokenID::Empty(id, sub) => write!(f, "{id}.{sub}"),
+        }
+    }
+}
+
+/// The morphological features of a token, as found in the FEATS column.
+///
+/// Features are kept in their original order, and [iter](Features::iter) yields them
+/// as they were read. Values of multi-valued features such as `PronType=Int,Rel`
+/// can be accessed individually via [values](Features::values), and layered
+/// features such as `Gender[psor]=Masc` via [get_layered](Features::get_layered).
+///
+/// The [Display] implementation produces the canonical form required by the
+/// CoNLL-U specification: features and the values of multi-valued features are
+/// sorted alphabetically, ignoring case. Consequently, two `Features` are equal
+/// if they contain the same features with the same values, regardless of the
+/// order of either.
+///
+/// ```rust
+/// use rs_conllu::token::Features;
+///
+/// let features: Features = "PronType=Rel,Int|Number[psor]=Sing|Case=Nom".parse().unwrap();
+///
+/// assert_eq!(features.get("Case"), Some("Nom"));
+/// assert_eq!(features.values("PronType").collect::<Vec<_>>(), vec!["Rel", "Int"]);
+/// assert_eq!(features.get_layered("Number", Some("psor")), Some("Sing"));
+/// assert_eq!(features.to_string(), "Case=Nom|Number[psor]=Sing|PronType=Int,Rel");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Features {
+    entries: Vec<(String, String)>,
+}
+
+impl Features {
+    pub fn new() -> Self {
+        Features::default()
+    }
+
+    /// Set the value of a feature, returning the previous value if the
+    /// feature was already present.
+    pub fn insert(&mut self, key: String, value: String) -> Option<String> {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Remove a feature, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// The complete value of a feature, e.g. `Int,Rel` for `PronType=Int,Rel`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The value of the feature `name` in the given layer, e.g. `Gender[psor]`
+    /// for `get_layered("Gender", Some("psor"))`.
+    pub fn get_layered(&self, name: &str, layer: Option<&str>) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| Features::split_layer(k) == (name, layer))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Iterate over the individual values of a multi-valued feature.
+    pub fn values(&self, key: &str) -> impl Iterator<Item = &str> {
+        self.get(key).into_iter().flat_map(|v| v.split(','))
+    }
+
+    /// Whether the feature `key` has `value` as one of its values.
+    pub fn has_value(&self, key: &str, value: &str) -> bool {
+        self.values(key).any(|v| v == value)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Split a feature name into the name proper and its layer,
+    /// e.g. `Gender[psor]` into `("Gender", Some("psor"))`.
+    pub fn split_layer(key: &str) -> (&str, Option<&str>) {
+        match key.strip_suffix(']').and_then(|k| k.split_once('[')) {
+            Some((name, layer)) => (name, Some(layer)),
+            None => (key, None),
+        }
+    }
+
+    /// Iterate over the features in their original order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Iterate over the features sorted alphabetically by name, ignoring case.
+    pub fn sorted(&self) -> impl Iterator<Item = (&str, &str)> {
+        let mut entries: Vec<(&str, &str)> = self.iter().collect();
+        entries.sort_by(|a, b| canonical_order(a.0, b.0));
+        entries.into_iter()
+    }
+
+    /// Whether the features and their values are in canonical order.
+    pub fn is_sorted(&self) -> bool {
+        self.entries
+            .windows(2)
+            .all(|w| canonical_order(&w[0].0, &w[1].0).is_lt())
+            && self.entries.iter().all(|(_, v)| {
+                let values: Vec<&str> = v.split(',').collect();
+                values
+                    .windows(2)
+                    .all(|w| canonical_order(w[0], w[1]).is_lt())
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Alphabetical order ignoring case, as used for features in CoNLL-U.
+fn canonical_order(a: &str, b: &str) -> Ordering {
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+/// The values of a multi-valued feature in canonical order.
+fn sorted_values(value: &str) -> Vec<&str> {
+    let mut values: Vec<&str> = value.split(',').collect();
+    values.sort_by(|a, b| canonical_order(a, b));
+    values
+}
+
+impl PartialEq for Features {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|(k, v)| {
+                other
+                    .get(k)
+                    .is_some_and(|w| sorted_values(v) == sorted_values(w))
+            })
+    }
+}
+
+impl Eq for Features {}
+
+impl FromStr for Features {
+    type Err = ParseErrorType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_key_value_pairs(s)
+    }
+}
+
+impl FromIterator<(String, String)> for Features {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        let mut features = Features::new();
+        for (key, value) in iter {
+            features.insert(key, value);
+        }
+        features
+    }
+}
+
+impl<const N: usize> From<[(String, String); N]> for Features {
+    fn from(entries: [(String, String); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+impl fmt::Display for Features {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.sorted().enumerate() {
+            if i > 0 {
+                f.write_str("|")?;
+            }
+            write!(f, "{key}={}", sorted_values(value).join(","))?;
+        }
+        Ok(())
+    }
+}
 
 /// A `Token` is the basic unit of what is defined on a (non-comment) line in CoNLL-U format.
 /// The ConLL-U specification uses the terms _word_, _node_ and _multi-word token_ while this crate
@@ -24,15 +234,19 @@ type Features = HashMap<String, String>;
 /// The fields of a `Token` are the ten fields that are defined in the CoNLL-U specification.
 /// The only mandatory fields are [id](Token::id) and [form](Token::form). The remaining ones are optional (absence denoted
 /// by an underscore in the text format) and represented as [Option] types.
+/// Additional columns of CoNLL-U Plus files are stored verbatim in
+/// [extra_columns](Token::extra_columns) and can be looked up via [extra](Token::extra).
 ///
 /// A [TokenBuilder] type is available for more convenient creation of [Token] structs,
 /// which can be instantiated via the [builder](Token::builder) method.
 ///
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token {
     /// The id of the token within the sentence.
     pub id: TokenID,
-    /// The surface form of the token as it appears in the sentence.
+    /// The surface form of the token as it appears in the sentence, or `_` if
+    /// unspecified. See [is_literal_underscore](Token::is_literal_underscore).
     pub form: String,
     /// The lemma or lexical form of the token.
     pub lemma: Option<String>,
@@ -49,7 +263,17 @@ pub struct Token {
     /// Enhanced dependency graph information.
     pub deps: Option<Vec<Dep>>,
     /// Other types of annotation.
-    pub misc: Option<String>,
+    pub misc: Option<Misc>,
+    /// Values of non-standard CoNLL-U Plus columns as pairs of column name and value.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Vec::is_empty",
+            with = "crate::serialization::pairs"
+        )
+    )]
+    pub extra_columns: Vec<(String, String)>,
 }
 
 impl Token {
@@ -57,6 +281,148 @@ impl Token {
     pub fn builder(id: TokenID, form: String) -> TokenBuilder {
         TokenBuilder::new(id, form)
     }
+
+    /// Whether the token is a literal underscore rather than a token with an
+    /// unspecified form. Following the UD convention, a FORM `_` together with
+    /// a LEMMA `_` is parsed as a literal underscore with the lemma `Some("_")`,
+    /// while the lemma of a FORM `_` with an unspecified form is `None`.
+    ///
+    /// ```rust
+    /// use rs_conllu::parse_token;
+    ///
+    /// let literal = parse_token("1\t_\t_\tPUNCT\t_\t_\t0\troot\t_\t_").unwrap();
+    /// assert_eq!(literal.lemma.as_deref(), Some("_"));
+    /// assert!(literal.is_literal_underscore());
+    /// assert_eq!(literal.to_string(), "1\t_\t_\tPUNCT\t_\t_\t0\troot\t_\t_");
+    ///
+    /// let unspecified = parse_token("1\t_\tgo\tVERB\t_\t_\t0\troot\t_\t_").unwrap();
+    /// assert!(!unspecified.is_literal_underscore());
+    /// ```
+    pub fn is_literal_underscore(&self) -> bool {
+        self.form == "_" && self.lemma.as_deref() == Some("_")
+    }
+
+    /// The value of a non-standard CoNLL-U Plus column.
+    ///
+    /// ```rust
+    /// use rs_conllu::{columns::Columns, parsers::parse_token_with_columns};
+    ///
+    /// let columns = Columns::parse("ID FORM PARSEME:MWE").unwrap();
+    /// let token = parse_token_with_columns("1\tkicked\t1:VID", &columns).unwrap();
+    ///
+    /// assert_eq!(token.extra("PARSEME:MWE"), Some("1:VID"));
+    /// ```
+    pub fn extra(&self, column: &str) -> Option<&str> {
+        self.extra_columns
+            .iter()
+            .find(|(name, _)| name == column)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Format the token as a line with the given columns, e.g. the columns
+    /// of a CoNLL-U Plus file. Columns the token has no value for are written
+    /// as an underscore.
+    ///
+    /// ```rust
+    /// use rs_conllu::{columns::Columns, Token, TokenID};
+    ///
+    /// let columns = Columns::parse("ID FORM PARSEME:MWE").unwrap();
+    /// let token = Token::builder(TokenID::Single(1), "kicked".to_string())
+    ///     .extra_column("PARSEME:MWE".to_string(), "1:VID".to_string())
+    ///     .build();
+    ///
+    /// assert_eq!(token.display_with(&columns).to_string(), "1\tkicked\t1:VID");
+    /// ```
+    pub fn display_with<'a>(&'a self, columns: &'a Columns<'a>) -> TokenDisplay<'a> {
+        TokenDisplay {
+            token: self,
+            columns,
+        }
+    }
+
+    fn fmt_column(&self, f: &mut fmt::Formatter<'_>, column: &Column<'_>) -> fmt::Result {
+        match column {
+            Column::Id => write!(f, "{}", self.id),
+            Column::Form => f.write_str(&self.form),
+            Column::Lemma => write_placeholder(f, self.lemma.as_ref()),
+            Column::Upos => write_placeholder(f, self.upos.as_ref()),
+            Column::Xpos => write_placeholder(f, self.xpos.as_ref()),
+            Column::Feats => match &self.features {
+                Some(features) => write!(f, "{features}"),
+                None => f.write_str("_"),
+            },
+            Column::Head => write_placeholder(f, self.head.as_ref()),
+            Column::Deprel => write_placeholder(f, self.deprel.as_ref()),
+            Column::Deps => match &self.deps {
+                Some(deps) => {
+                    for (i, dep) in deps.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str("|")?;
+                        }
+                        write!(f, "{dep}")?;
+                    }
+                    Ok(())
+                }
+                None => f.write_str("_"),
+            },
+            Column::Misc => write_placeholder(f, self.misc.as_ref()),
+            Column::Extra(name) => write_placeholder(f, self.extra(name)),
+        }
+    }
+}
+
+/// Formats a [Token] with a custom list of columns, see [Token::display_with].
+pub struct TokenDisplay<'a> {
+    token: &'a Token,
+    columns: &'a Columns<'a>,
+}
+
+impl fmt::Display for TokenDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, column) in self.columns.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\t")?;
+            }
+            self.token.fmt_column(f, column)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the token as a single line in CoNLL-U format (without the trailing newline).
+/// Absent fields are written as an underscore and [Features] are written in
+/// canonical order, as required by the specification. Values of
+/// [extra columns](Token::extra_columns) follow the ten standard columns.
+///
+/// A [literal underscore](Token::is_literal_underscore) is written as FORM and
+/// LEMMA `_`, so that it is read back unchanged. The format cannot express a
+/// FORM `_` without a lemma or a LEMMA `_` of any other form: both are read
+/// back differently.
+///
+/// ```rust
+/// use rs_conllu::{Token, TokenID, UPOS};
+///
+/// let token = Token::builder(TokenID::Single(1), "Hello".to_string())
+///     .upos(UPOS::INTJ)
+///     .build();
+///
+/// assert_eq!(token.to_string(), "1\tHello\t_\tINTJ\t_\t_\t_\t_\t_\t_");
+/// ```
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(&Columns::standard()))?;
+        for (_, value) in &self.extra_columns {
+            write!(f, "\t{value}")?;
+        }
+        Ok(())
+    }
+}
+
+fn write_placeholder<T: fmt::Display>(f: &mut fmt::Formatter<'_>, field: Option<T>) -> fmt::Result {
+    match field {
+        Some(value) => write!(f, "{value}"),
+        None => f.write_str("_"),
+    }
 }
 
 /// A builder for Tokens to allow for more convenient manual creation if necessary.
@@ -80,7 +446,8 @@ pub struct TokenBuilder {
     head: Option<TokenID>,
     deprel: Option<String>,
     deps: Option<Vec<Dep>>,
-    misc: Option<String>,
+    misc: Option<Misc>,
+    extra_columns: Vec<(String, String)>,
 }
 
 impl TokenBuilder {
@@ -98,6 +465,7 @@ impl TokenBuilder {
             deprel: None,
             deps: None,
             misc: None,
+            extra_columns: Vec::new(),
         }
     }
 
@@ -144,11 +512,17 @@ impl TokenBuilder {
     }
 
     /// Set the misc field.
-    pub fn misc(mut self, misc: String) -> TokenBuilder {
+    pub fn misc(mut self, misc: Misc) -> TokenBuilder {
         self.misc = Some(misc);
         self
     }
 
+    /// Add the value of a non-standard CoNLL-U Plus column.
+    pub fn extra_column(mut self, column: String, value: String) -> TokenBuilder {
+        self.extra_columns.push((column, value));
+        self
+    }
+
     /// Build the token.
     pub fn build(self) -> Token {
         Token {
@@ -162,14 +536,368 @@ impl TokenBuilder {
             deprel: self.deprel,
             deps: self.deps,
             misc: self.misc,
+            extra_columns: self.extra_columns,
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dep {
     /// The head of the relation.
     pub head: TokenID,
     /// The type of the relation.
     pub rel: String,
 }
+
+impl fmt::Display for Dep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.head, self.rel)
+    }
+}
+
+/// The MISC column of a token: an ordered list of `|`-separated entries, which
+/// are usually `key=value` attributes but may also be bare items.
+///
+/// The attributes documented by the [UD guidelines](https://universaldependencies.org/misc.html)
+/// have typed accessors, while all entries, including unknown ones, are preserved
+/// in their original order for writing.
+///
+/// ```rust
+/// use rs_conllu::token::Misc;
+///
+/// let misc = Misc::from("SpaceAfter=No|Translit=kniga|TokenRange=4:9|Checked");
+///
+/// assert!(!misc.space_after());
+/// assert_eq!(misc.translit(), Some("kniga"));
+/// assert_eq!(misc.token_range(), Some((4, 9)));
+/// assert!(misc.contains("Checked"));
+/// assert_eq!(misc.to_string(), "SpaceAfter=No|Translit=kniga|TokenRange=4:9|Checked");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Misc {
+    entries: Vec<MiscEntry>,
+}
+
+/// A single entry of the MISC column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiscEntry {
+    pub key: String,
+    /// The value of the attribute, or `None` for a bare item without `=`.
+    pub value: Option<String>,
+}
+
+impl Misc {
+    pub fn new() -> Self {
+        Misc::default()
+    }
+
+    /// The value of the first attribute with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.key == key)
+            .and_then(|e| e.value.as_deref())
+    }
+
+    /// Whether an attribute or bare item with the given key is present.
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.iter().any(|e| e.key == key)
+    }
+
+    /// Set the value of an attribute. Replaces the value of the first
+    /// entry with the same key, or appends a new one.
+    pub fn set(&mut self, key: &str, value: String) {
+        match self.entries.iter_mut().find(|e| e.key == key) {
+            Some(entry) => entry.value = Some(value),
+            None => self.entries.push(MiscEntry {
+                key: key.to_string(),
+                value: Some(value),
+            }),
+        }
+    }
+
+    /// Remove all entries with the given key.
+    pub fn remove(&mut self, key: &str) {
+        self.entries.retain(|e| e.key != key);
+    }
+
+    /// Append an entry.
+    pub fn push(&mut self, entry: MiscEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Iterate over all entries in their original order.
+    pub fn iter(&self) -> std::slice::Iter<'_, MiscEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the token is followed by a space, i.e. `false` only for `SpaceAfter=No`.
+    pub fn space_after(&self) -> bool {
+        self.get("SpaceAfter") != Some("No")
+    }
+
+    /// The whitespace following the token given by `SpacesAfter`, with the
+    /// escape sequences `\s`, `\t`, `\n`, `\r`, `\p` and `\\` resolved.
+    pub fn spaces_after(&self) -> Option<String> {
+        self.get("SpacesAfter").map(unescape_spaces)
+    }
+
+    /// The whitespace preceding the token given by `SpacesBefore`, with escape
+    /// sequences resolved as in [spaces_after](Misc::spaces_after).
+    pub fn spaces_before(&self) -> Option<String> {
+        self.get("SpacesBefore").map(unescape_spaces)
+    }
+
+    /// The transliteration of the word form (`Translit`).
+    pub fn translit(&self) -> Option<&str> {
+        self.get("Translit")
+    }
+
+    /// The transliteration of the lemma (`LTranslit`).
+    pub fn ltranslit(&self) -> Option<&str> {
+        self.get("LTranslit")
+    }
+
+    /// The English gloss of the word (`Gloss`).
+    pub fn gloss(&self) -> Option<&str> {
+        self.get("Gloss")
+    }
+
+    /// The corrected form of a misspelled word (`CorrectForm`).
+    pub fn correct_form(&self) -> Option<&str> {
+        self.get("CorrectForm")
+    }
+
+    /// The character offsets of the token in the original text (`TokenRange=start:end`).
+    pub fn token_range(&self) -> Option<(usize, usize)> {
+        let (start, end) = self.get("TokenRange")?.split_once(':')?;
+        Some((start.parse().ok()?, end.parse().ok()?))
+    }
+}
+
+fn unescape_spaces(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('p') => unescaped.push('|'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+impl FromStr for Misc {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Misc::from(s))
+    }
+}
+
+impl From<&str> for Misc {
+    fn from(field: &str) -> Self {
+        let entries = field
+            .split('|')
+            .map(|item| match item.split_once('=') {
+                Some((key, value)) => MiscEntry {
+                    key: key.to_string(),
+                    value: Some(value.to_string()),
+                },
+                None => MiscEntry {
+                    key: item.to_string(),
+                    value: None,
+                },
+            })
+            .collect();
+
+        Misc { entries }
+    }
+}
+
+impl FromIterator<MiscEntry> for Misc {
+    fn from_iter<I: IntoIterator<Item = MiscEntry>>(iter: I) -> Self {
+        Misc {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Misc {
+    type Item = &'a MiscEntry;
+    type IntoIter = std::slice::Iter<'a, MiscEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl fmt::Display for Misc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str("|")?;
+            }
+            write!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MiscEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}={value}", self.key),
+            None => f.write_str(&self.key),
+        }
+    }
+}
+
+/// A zero-copy variant of [Token] that borrows all of its textual fields
+/// from the input it was parsed from. Produced by
+/// [parse_token_borrowed](crate::parsers::parse_token_borrowed).
+///
+/// The [features](BorrowedToken::features) and [deps](BorrowedToken::deps) fields
+/// hold the raw, already validated column content. Use
+/// [iter_features](BorrowedToken::iter_features) and [iter_deps](BorrowedToken::iter_deps)
+/// to access the individual entries without allocating, or convert the token into
+/// a [Token] via [to_token](BorrowedToken::to_token).
+///
+/// ```rust
+/// use rs_conllu::{parsers::parse_token_borrowed, TokenID};
+///
+/// let line = "1\tThey\tthey\tPRON\tPRP\tCase=Nom|Number=Plur\t2\tnsubj\t2:nsubj|4:nsubj\t_";
+/// let token = parse_token_borrowed(line).unwrap();
+///
+/// assert_eq!(token.form, "They");
+/// assert_eq!(token.iter_features().map(|f| f.0).collect::<Vec<_>>(), vec!["Case", "Number"]);
+/// assert_eq!(token.iter_deps().nth(1).unwrap().head, TokenID::Single(4));
+/// assert_eq!(token.to_token().lemma, Some("they".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BorrowedToken<'a> {
+    /// The id of the token within the sentence.
+    pub id: TokenID,
+    /// The surface form of the token as it appears in the sentence, or `_` if
+    /// unspecified. See [is_literal_underscore](BorrowedToken::is_literal_underscore).
+    pub form: &'a str,
+    /// The lemma or lexical form of the token.
+    pub lemma: Option<&'a str>,
+    /// The universal POS tag of the token.
+    pub upos: Option<UPOS>,
+    /// Language-specific POS tag for the token.
+    pub xpos: Option<&'a str>,
+    /// The raw morphological features column.
+    pub features: Option<&'a str>,
+    /// The head of the current token.
+    pub head: Option<TokenID>,
+    /// The dependency relation fo the token.
+    pub deprel: Option<&'a str>,
+    /// The raw enhanced dependency column.
+    pub deps: Option<&'a str>,
+    /// Other types of annotation.
+    pub misc: Option<&'a str>,
+    /// Values of non-standard CoNLL-U Plus columns as pairs of column name and value.
+    pub extra_columns: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> BorrowedToken<'a> {
+    /// The value of a non-standard CoNLL-U Plus column.
+    pub fn extra(&self, column: &str) -> Option<&'a str> {
+        self.extra_columns
+            .iter()
+            .find(|(name, _)| *name == column)
+            .map(|(_, value)| *value)
+    }
+
+    /// Whether the token is a literal underscore, see [Token::is_literal_underscore].
+    pub fn is_literal_underscore(&self) -> bool {
+        self.form == "_" && self.lemma == Some("_")
+    }
+
+    /// Iterate over the morphological features as key-value pairs.
+    pub fn iter_features(&self) -> impl Iterator<Item = Feature<'a>> {
+        self.features.into_iter().flat_map(split_features)
+    }
+
+    /// Iterate over the enhanced dependencies.
+    pub fn iter_deps(&self) -> impl Iterator<Item = BorrowedDep<'a>> {
+        self.deps.into_iter().flat_map(split_deps)
+    }
+
+    /// Convert into an owned [Token], allocating all textual fields.
+    pub fn to_token(&self) -> Token {
+        Token {
+            id: self.id,
+            form: self.form.to_string(),
+            lemma: self.lemma.map(String::from),
+            upos: self.upos,
+            xpos: self.xpos.map(String::from),
+            features: self.features.map(|_| {
+                self.iter_features()
+                    .map(|Feature(k, v)| (k.to_string(), v.to_string()))
+                    .collect()
+            }),
+            head: self.head,
+            deprel: self.deprel.map(String::from),
+            deps: self
+                .deps
+                .map(|_| self.iter_deps().map(BorrowedDep::to_dep).collect()),
+            misc: self.misc.map(Misc::from),
+            extra_columns: self
+                .extra_columns
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl From<BorrowedToken<'_>> for Token {
+    fn from(token: BorrowedToken<'_>) -> Self {
+        token.to_token()
+    }
+}
+
+/// A zero-copy variant of [Dep], as yielded by [BorrowedToken::iter_deps].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorrowedDep<'a> {
+    /// The head of the relation.
+    pub head: TokenID,
+    /// The type of the relation.
+    pub rel: &'a str,
+}
+
+impl BorrowedDep<'_> {
+    /// Convert into an owned [Dep].
+    pub fn to_dep(self) -> Dep {
+        Dep {
+            head: self.head,
+            rel: self.rel.to_string(),
+        }
+    }
+}
+
+impl From<BorrowedDep<'_>> for Dep {
+    fn from(dep: BorrowedDep<'_>) -> Self {
+        dep.to_dep()
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::{Sentence, Token, TokenID};
+
+/// A view of the basic dependency tree of a [Sentence], as given by the
+/// HEAD column. Created via [Sentence::tree].
+///
+/// Nodes are identified by their word index, i.e. `n` for a token with id
+/// [TokenID::Single(n)](TokenID::Single). The artificial root node `0` is part of the
+/// tree and is the parent of the sentence root. Multiword tokens are not part of
+/// the tree, and neither are empty nodes, which only take part in the enhanced
+/// dependency graph; they are available via [empty_nodes](Tree::empty_nodes).
+/// Words without a HEAD are detached from the tree.
+///
+/// All methods are robust against malformed input: HEADs pointing to unknown
+/// words are ignored and cycles do not lead to endless iteration.
+///
+/// ```rust
+/// use rs_conllu::parse_sentence;
+///
+/// let sentence = parse_sentence(
+///     "1\tThey\tthey\tPRON\t_\t_\t2\tnsubj\t_\t_
+/// 2\tbuy\tbuy\tVERB\t_\t_\t0\troot\t_\t_
+/// 3\tnew\tnew\tADJ\t_\t_\t4\tamod\t_\t_
+/// 4\tbooks\tbook\tNOUN\t_\t_\t2\tobj\t_\t_
+/// ",
+/// )
+/// .unwrap();
+/// let tree = sentence.tree();
+///
+/// assert_eq!(tree.root().unwrap().form, "buy");
+/// assert_eq!(tree.children(2), &[1, 4]);
+/// assert_eq!(tree.parent(3), Some(4));
+/// assert_eq!(tree.depth(3), Some(3));
+/// assert_eq!(tree.subtree_span(4), Some((3, 4)));
+/// assert_eq!(tree.lowest_common_ancestor(1, 3), Some(2));
+/// ```
+pub struct Tree<'a> {
+    words: BTreeMap<usize, &'a Token>,
+    children: HashMap<usize, Vec<usize>>,
+    empty_nodes: Vec<&'a Token>,
+}
+
+impl<'a> Tree<'a> {
+    pub fn new(sentence: &'a Sentence) -> Self {
+        let mut words = BTreeMap::new();
+        let mut empty_nodes = Vec::new();
+
+        for token in &sentence.tokens {
+            match token.id {
+                TokenID::Single(id) => {
+                    words.insert(id, token);
+                }
+                TokenID::Empty(_, _) => empty_nodes.push(token),
+                TokenID::Range(_, _) => {}
+            }
+        }
+
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (id, token) in &words {
+            if let Some(TokenID::Single(head)) = token.head {
+                if head == 0 || words.contains_key(&head) {
+                    children.entry(head).or_default().push(*id);
+                }
+            }
+        }
+
+        Tree {
+            words,
+            children,
+            empty_nodes,
+        }
+    }
+
+    /// The word attached to the artificial root node. If there are several,
+    /// the first one is returned.
+    pub fn root(&self) -> Option<&'a Token> {
+        self.children(0).first().and_then(|id| self.token(*id))
+    }
+
+    /// The word with the given index.
+    pub fn token(&self, id: usize) -> Option<&'a Token> {
+        self.words.get(&id).copied()
+    }
+
+    /// The empty nodes of the sentence, which are not part of the basic tree.
+    pub fn empty_nodes(&self) -> &[&'a Token] {
+        &self.empty_nodes
+    }
+
+    /// The parent of a word, which is `0` for the sentence root.
+    /// Returns `None` for the artificial root node, unknown and detached words.
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        match self.token(id)?.head {
+            Some(TokenID::Single(head)) if head == 0 || self.words.contains_key(&head) => {
+                Some(head)
+            }
+            _ => None,
+        }
+    }
+
+    /// The children of a node in ascending order.
+    pub fn children(&self, id: usize) -> &[usize] {
+        self.children
+            .get(&id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterate over the ancestors of a node, starting with its parent and
+    /// ending with the artificial root node if it is reachable.
+    pub fn ancestors(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut current = id;
+        let mut seen = HashSet::from([id]);
+
+        std::iter::from_fn(move || {
+            let parent = self.parent(current)?;
+            if !seen.insert(parent) {
+                return None;
+            }
+            current = parent;
+            Some(parent)
+        })
+    }
+
+    /// Iterate over the nodes dominated by a node in pre-order,
+    /// not including the node itself.
+    pub fn descendants(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut stack: Vec<usize> = self.children(id).iter().rev().copied().collect();
+        let mut seen = HashSet::from([id]);
+
+        std::iter::from_fn(move || loop {
+            let next = stack.pop()?;
+            if seen.insert(next) {
+                stack.extend(self.children(next).iter().rev());
+                return Some(next);
+            }
+        })
+    }
+
+    /// The smallest and largest word index of the subtree rooted in a word.
+    pub fn subtree_span(&self, id: usize) -> Option<(usize, usize)> {
+        self.token(id)?;
+
+        let span = self
+            .descendants(id)
+            .fold((id, id), |(min, max), d| (min.min(d), max.max(d)));
+        Some(span)
+    }
+
+    /// Iterate over all words attached to the tree in pre-order, i.e.
+    /// depth-first with children in ascending order.
+    pub fn preorder(&self) -> impl Iterator<Item = usize> + '_ {
+        self.descendants(0)
+    }
+
+    /// Iterate over all words attached to the tree in breadth-first order.
+    pub fn breadth_first(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut queue: VecDeque<usize> = self.children(0).iter().copied().collect();
+        let mut seen = HashSet::from([0]);
+
+        std::iter::from_fn(move || loop {
+            let next = queue.pop_front()?;
+            if seen.insert(next) {
+                queue.extend(self.children(next));
+                return Some(next);
+            }
+        })
+    }
+
+    /// The number of edges between a node and the artificial root node,
+    /// i.e. `1` for the sentence root. Returns `None` if the node is not
+    /// connected to the root.
+    pub fn depth(&self, id: usize) -> Option<usize> {
+        if id == 0 {
+            return Some(0);
+        }
+
+        let mut depth = 0;
+        for ancestor in self.ancestors(id) {
+            depth += 1;
+            if ancestor == 0 {
+                return Some(depth);
+            }
+        }
+        None
+    }
+
+    /// The lowest node that dominates both `a` and `b` (or is one of them).
+    /// This is the artificial root node `0` if the nodes are only connected through it.
+    pub fn lowest_common_ancestor(&self, a: usize, b: usize) -> Option<usize> {
+        if a != 0 {
+            self.token(a)?;
+        }
+        if b != 0 {
+            self.token(b)?;
+        }
+
+        let path: HashSet<usize> = std::iter::once(a).chain(self.ancestors(a)).collect();
+        std::iter::once(b)
+            .chain(self.ancestors(b))
+            .find(|n| path.contains(n))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse_sentence;
+
+    const SENTENCE: &str = "1-2\tvámonos\t_\t_\t_\t_\t_\t_\t_\t_
+1\tvamos\tir\tVERB\t_\t_\t0\troot\t0:root\t_
+2\tnos\tnosotros\tPRON\t_\t_\t1\tobj\t1:obj\t_
+3\ta\ta\tADP\t_\t_\t4\tcase\t4:case\t_
+4\tla\tel\tDET\t_\t_\t5\tdet\t5:det\t_
+5\tplaya\tplaya\tNOUN\t_\t_\t1\tobl\t1:obl\t_
+5.1\tvamos\tir\tVERB\t_\t_\t_\t_\t1:conj\t_
+6\t.\t.\tPUNCT\t_\t_\t1\tpunct\t1:punct\t_
+";
+
+    #[test]
+    fn test_traversals() {
+        let sentence = parse_sentence(SENTENCE).unwrap();
+        let tree = sentence.tree();
+
+        assert_eq!(tree.preorder().collect::<Vec<_>>(), vec![1, 2, 5, 4, 3, 6]);
+        assert_eq!(
+            tree.breadth_first().collect::<Vec<_>>(),
+            vec![1, 2, 5, 6, 4, 3]
+        );
+        assert_eq!(tree.ancestors(3).collect::<Vec<_>>(), vec![4, 5, 1, 0]);
+        assert_eq!(tree.descendants(5).collect::<Vec<_>>(), vec![4, 3]);
+        assert_eq!(tree.empty_nodes().len(), 1);
+        assert_eq!(tree.lowest_common_ancestor(3, 6), Some(1));
+        assert_eq!(tree.lowest_common_ancestor(4, 5), Some(5));
+    }
+
+    #[test]
+    fn test_cycle_terminates() {
+        let sentence = parse_sentence(
+            "1\ta\t_\t_\t_\t_\t2\tdep\t_\t_
+2\tb\t_\t_\t_\t_\t1\tdep\t_\t_
+",
+        )
+        .unwrap();
+        let tree = sentence.tree();
+
+        assert_eq!(tree.root(), None);
+        assert_eq!(tree.ancestors(1).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(tree.depth(1), None);
+        assert_eq!(tree.descendants(1).collect::<Vec<_>>(), vec![2]);
+    }
+}
@@ -0,0 +1,835 @@
+//! Validation of sentences against the rules of the CoNLL-U specification
+//! and the Universal Dependencies guidelines.
+//!
+//! The checks follow the levels of the official UD validation script:
+//! level 1 covers the format of the file, level 2 the format of the annotation
+//! (IDs, HEADs, features, relations, sentence metadata) and level 3 selected
+//! universal constraints on the content of the annotation.
+//!
+//! ```rust
+//! use rs_conllu::{parse_sentence, validate::Validator};
+//!
+//! let sentence = parse_sentence(
+//!     "# sent_id = 1
+//! ## text = Hello world
+//! 1\tHello\thello\tINTJ\t_\t_\t0\troot\t0:root\t_
+//! 2\tworld\tworld\tNOUN\t_\t_\t1\tvocative\t1:voc\t_
+//! ",
+//! )
+//! .unwrap();
+//!
+//! let findings = Validator::new().validate_sentence(&sentence, 1);
+//!
+//! assert_eq!(findings.len(), 1);
+//! assert_eq!(findings[0].rule, "deps-missing-head");
+//! assert_eq!(findings[0].line, 4);
+//! ```
+
+use std::{collections::HashMap, fmt, io::BufRead};
+
+use crate::{
+    parsers::{ConlluParseError, Doc, DocError, SentenceLines},
+    MetaEntry, Sentence, Token, TokenID, UPOS,
+};
+
+/// The universal dependency relations of UD version 2.
+pub const UNIVERSAL_DEPRELS: [&str; 37] = [
+    "acl",
+    "advcl",
+    "advmod",
+    "amod",
+    "appos",
+    "aux",
+    "case",
+    "cc",
+    "ccomp",
+    "clf",
+    "compound",
+    "conj",
+    "cop",
+    "csubj",
+    "dep",
+    "det",
+    "discourse",
+    "dislocated",
+    "expl",
+    "fixed",
+    "flat",
+    "goeswith",
+    "iobj",
+    "list",
+    "mark",
+    "nmod",
+    "nsubj",
+    "nummod",
+    "obj",
+    "obl",
+    "orphan",
+    "parataxis",
+    "punct",
+    "reparandum",
+    "root",
+    "vocative",
+    "xcomp",
+];
+
+/// A single violation of a validation rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The validation level the rule belongs to, from 1 to 3.
+    pub level: u8,
+    /// A short identifier of the violated rule, e.g. `multiple-roots`.
+    pub rule: &'static str,
+    /// The `sent_id` of the affected sentence, if known.
+    pub sent_id: Option<String>,
+    /// The line number of the affected line.
+    pub line: usize,
+    /// The 1-based character position within the line, if known. Unlike
+    /// [ConlluParseError::column], this is not a field index.
+    pub char_column: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[Line {}", self.line)?;
+        if let Some(sent_id) = &self.sent_id {
+            write!(f, " Sent {sent_id}")?;
+        }
+        write!(f, "]: [L{} {}] {}", self.level, self.rule, self.message)
+    }
+}
+
+/// A `Validator` checks sentences one by one and keeps track of the
+/// document-wide state, such as the `sent_id`s seen so far.
+///
+/// Line numbers refer to the position of the sentence in its file. Sentences
+/// read by [Validator::validate_doc] are reported on the lines they were read
+/// from; a sentence passed to [Validator::validate_sentence] is expected to
+/// have its comment lines first, followed by one line per token.
+pub struct Validator {
+    max_level: u8,
+    sent_ids: HashMap<String, usize>,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Validator::new()
+    }
+}
+
+impl Validator {
+    /// A validator that checks all levels.
+    pub fn new() -> Self {
+        Validator::with_level(3)
+    }
+
+    /// A validator that only checks rules up to and including `level`.
+    pub fn with_level(level: u8) -> Self {
+        Validator {
+            max_level: level,
+            sent_ids: HashMap::new(),
+        }
+    }
+
+    /// Validate all sentences of a [Doc]. Sentences that cannot be read or
+    /// parsed are reported as level 1 findings, with one finding for every line
+    /// that cannot be parsed. Other rules are only checked for sentences
+    /// without parse errors.
+    pub fn validate_doc<T: BufRead>(&mut self, mut doc: Doc<T>) -> Vec<Finding> {
+        let mut findings = vec![];
+
+        while let Some(sentence) = doc.next_recovering() {
+            match sentence {
+                Ok(sentence) if sentence.is_complete() => {
+                    let lines = sentence.lines.clone();
+                    let sentence = sentence.into_sentence();
+                    findings.extend(self.check_sentence(&sentence, doc.sentence_line(), lines))
+                }
+                Ok(sentence) => findings.extend(sentence.errors().map(|e| {
+                    let mut finding = Finding::from_parse_error(e);
+                    finding.sent_id = sentence.meta.sent_id().map(String::from);
+                    finding
+                })),
+                Err(e) => findings.push(Finding::from_doc_error(&e, doc.sentence_line())),
+            }
+        }
+
+        findings
+    }
+
+    /// Validate a single sentence whose first line is `line`.
+    pub fn validate_sentence(&mut self, sentence: &Sentence, line: usize) -> Vec<Finding> {
+        let lines = SentenceLines::contiguous(line, sentence.meta.len(), sentence.tokens.len());
+        self.check_sentence(sentence, line, lines)
+    }
+
+    fn check_sentence(
+        &mut self,
+        sentence: &Sentence,
+        line: usize,
+        lines: SentenceLines,
+    ) -> Vec<Finding> {
+        let mut check = SentenceCheck {
+            sentence,
+            line,
+            lines,
+            sent_id: sentence.meta.sent_id().map(String::from),
+            max_level: self.max_level,
+            findings: vec![],
+        };
+
+        check.ids();
+        check.metadata(&mut self.sent_ids);
+        if self.max_level >= 2 {
+            check.heads();
+            check.fields();
+            check.text();
+        }
+        if self.max_level >= 3 {
+            check.content();
+        }
+
+        check.findings
+    }
+}
+
+impl Finding {
+    fn from_parse_error(error: &ConlluParseError) -> Finding {
+        let before = &error.line_text()[..error.span().start];
+        Finding {
+            level: 1,
+            rule: "parse-error",
+            sent_id: None,
+            line: error.line(),
+            char_column: Some(before.chars().count() + 1),
+            message: error.to_string(),
+        }
+    }
+
+    fn from_doc_error(error: &DocError, sentence_line: usize) -> Finding {
+        let (rule, line) = match error {
+            DocError::Io(_) => ("io-error", sentence_line),
+            DocError::Encoding { line, .. } => ("invalid-encoding", *line),
+            DocError::Parse(e) => return Finding::from_parse_error(e),
+        };
+        Finding {
+            level: 1,
+            rule,
+            sent_id: None,
+            line,
+            char_column: None,
+            message: error.to_string(),
+        }
+    }
+}
+
+struct SentenceCheck<'a> {
+    sentence: &'a Sentence,
+    line: usize,
+    lines: SentenceLines,
+    sent_id: Option<String>,
+    max_level: u8,
+    findings: Vec<Finding>,
+}
+
+impl<'a> SentenceCheck<'a> {
+    fn report(&mut self, level: u8, rule: &'static str, line: usize, message: String) {
+        if level <= self.max_level {
+            self.findings.push(Finding {
+                level,
+                rule,
+                sent_id: self.sent_id.clone(),
+                line,
+                char_column: None,
+                message,
+            });
+        }
+    }
+
+    /// The lines and values of the attributes named `key`.
+    fn attribute_lines(&self, key: &'a str) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.sentence
+            .meta
+            .iter()
+            .zip(&self.lines.meta)
+            .filter_map(move |(entry, line)| match entry {
+                MetaEntry::Pair { key: k, value, .. } if k == key => Some((*line, value.as_str())),
+                _ => None,
+            })
+    }
+
+    /// Whether the DEPS of the node `from` contain an edge from `to`.
+    fn enhanced_edge(&self, from: TokenID, to: TokenID) -> bool {
+        self.sentence
+            .tokens
+            .iter()
+            .find(|t| t.id == from)
+            .and_then(|t| t.deps.as_ref())
+            .is_some_and(|deps| deps.iter().any(|d| d.head == to))
+    }
+
+    /// The line of the token at `index` in the token list.
+    fn token_line(&self, index: usize) -> usize {
+        self.lines.tokens[index]
+    }
+
+    fn words(&self) -> impl Iterator<Item = (usize, &'a Token)> + 'a {
+        let sentence = self.sentence;
+        sentence
+            .tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| matches!(t.id, TokenID::Single(_)))
+    }
+
+    fn word_count(&self) -> usize {
+        self.words().count()
+    }
+
+    /// Word ids, multiword token ranges and empty node placement.
+    fn ids(&mut self) {
+        let word_count = self.word_count();
+        let mut last_word = 0;
+        let mut last_empty = (0, 0);
+        let mut last_range_end = 0;
+
+        if self.sentence.tokens.is_empty() {
+            self.report(
+                1,
+                "empty-sentence",
+                self.line,
+                "Sentence has no tokens".into(),
+            );
+        }
+
+        for (i, token) in self.sentence.tokens.iter().enumerate() {
+            let line = self.token_line(i);
+            match token.id {
+                TokenID::Single(id) => {
+                    if id != last_word + 1 {
+                        self.report(
+                            1,
+                            "word-id-sequence",
+                            line,
+                            format!("Expected word id {}, found {id}", last_word + 1),
+                        );
+                    }
+                    last_word = id;
+                }
+                TokenID::Range(start, end) => {
+                    if start >= end {
+                        self.report(
+                            1,
+                            "invalid-range",
+                            line,
+                            format!("Range {} must span at least two words", token.id),
+                        );
+                    } else if end > word_count {
+                        self.report(
+                            1,
+                            "invalid-range",
+                            line,
+                            format!("Range {} exceeds the last word {word_count}", token.id),
+                        );
+                    }
+                    if start != last_word + 1 {
+                        self.report(
+                            1,
+                            "misplaced-range",
+                            line,
+                            format!("Range {} must directly precede word {start}", token.id),
+                        );
+                    }
+                    if start <= last_range_end {
+                        self.report(
+                            1,
+                            "overlapping-range",
+                            line,
+                            format!("Range {} overlaps the preceding range", token.id),
+                        );
+                    }
+                    last_range_end = last_range_end.max(end);
+                }
+                TokenID::Empty(word, sub) => {
+                    let expected_sub = if last_empty.0 == word && last_word == word {
+                        last_empty.1 + 1
+                    } else {
+                        1
+                    };
+                    if word != last_word || sub != expected_sub {
+                        self.report(
+                            1,
+                            "misplaced-empty-node",
+                            line,
+                            format!(
+                                "Expected empty node {last_word}.{}, found {}",
+                                if word == last_word { expected_sub } else { 1 },
+                                token.id
+                            ),
+                        );
+                    }
+                    last_empty = (word, sub);
+                }
+            }
+        }
+    }
+
+    /// Presence and uniqueness of `sent_id` and `text`.
+    fn metadata(&mut self, sent_ids: &mut HashMap<String, usize>) {
+        let sent_id_lines: Vec<(usize, &str)> = self.attribute_lines("sent_id").collect();
+        let text_count = self.attribute_lines("text").count();
+
+        match sent_id_lines.as_slice() {
+            [] => self.report(2, "missing-sent-id", self.line, "Missing sent_id".into()),
+            [(line, sent_id), rest @ ..] => {
+                if !rest.is_empty() {
+                    self.report(
+                        2,
+                        "multiple-sent-id",
+                        *line,
+                        "Multiple sent_id attributes".into(),
+                    );
+                }
+                if let Some(first) = sent_ids.get(*sent_id) {
+                    self.report(
+                        2,
+                        "duplicate-sent-id",
+                        *line,
+                        format!("sent_id {sent_id} was already used in line {first}"),
+                    );
+                } else {
+                    sent_ids.insert(sent_id.to_string(), *line);
+                }
+            }
+        }
+
+        match text_count {
+            0 => self.report(2, "missing-text", self.line, "Missing text".into()),
+            1 => {}
+            _ => self.report(
+                2,
+                "multiple-text",
+                self.line,
+                "Multiple text attributes".into(),
+            ),
+        }
+    }
+
+    /// Exactly one root, existing heads and no cycles.
+    fn heads(&mut self) {
+        let words: HashMap<usize, &Token> = self
+            .words()
+            .filter_map(|(_, t)| match t.id {
+                TokenID::Single(id) => Some((id, t)),
+                _ => None,
+            })
+            .collect();
+        let mut roots = vec![];
+
+        for (i, token) in self.sentence.tokens.iter().enumerate() {
+            let line = self.token_line(i);
+            match (token.id, token.head) {
+                (TokenID::Single(_), None) => {
+                    self.report(2, "missing-head", line, "Word has no HEAD".into())
+                }
+                (TokenID::Single(id), Some(TokenID::Single(head))) => {
+                    if head == 0 {
+                        roots.push(line);
+                    } else if head == id {
+                        self.report(2, "head-self-loop", line, "Word is its own HEAD".into());
+                    } else if !words.contains_key(&head) {
+                        self.report(
+                            2,
+                            "unknown-head",
+                            line,
+                            format!("HEAD {head} does not refer to a word"),
+                        );
+                    }
+                }
+                (TokenID::Single(_), Some(head)) => self.report(
+                    2,
+                    "unknown-head",
+                    line,
+                    format!("HEAD {head} does not refer to a word"),
+                ),
+                (_, Some(_)) => self.report(
+                    2,
+                    "head-not-word",
+                    line,
+                    format!("Only words may have a HEAD, found one on {}", token.id),
+                ),
+                (_, None) => {}
+            }
+        }
+
+        match roots.as_slice() {
+            [] if !words.is_empty() => {
+                self.report(2, "no-root", self.line, "Sentence has no root".into())
+            }
+            [_, rest @ ..] => {
+                for line in rest {
+                    self.report(
+                        2,
+                        "multiple-roots",
+                        *line,
+                        "Sentence has multiple roots".into(),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        let tree = self.sentence.tree();
+        let mut reported: Vec<usize> = vec![];
+        for (i, token) in self.sentence.tokens.iter().enumerate() {
+            let TokenID::Single(id) = token.id else {
+                continue;
+            };
+            // the chain of ancestors ends early if the head of its last
+            // element is already part of the chain
+            let chain: Vec<usize> = std::iter::once(id).chain(tree.ancestors(id)).collect();
+            let start = chain
+                .last()
+                .and_then(|last| tree.parent(*last))
+                .and_then(|parent| chain.iter().position(|n| *n == parent));
+            let Some(start) = start else {
+                continue;
+            };
+            let cycle = &chain[start..];
+            // self loops are reported above
+            if cycle.len() < 2 || reported.contains(&cycle[0]) {
+                continue;
+            }
+            reported.extend(cycle);
+            let cycle = cycle.iter().map(usize::to_string).collect::<Vec<_>>();
+            self.report(
+                2,
+                "head-cycle",
+                self.token_line(i),
+                format!("HEADs form a cycle: {}", cycle.join(" -> ")),
+            );
+        }
+    }
+
+    /// Features, relations and enhanced dependencies.
+    fn fields(&mut self) {
+        for (i, token) in self.sentence.tokens.iter().enumerate() {
+            let line = self.token_line(i);
+
+            if let Some(features) = &token.features {
+                if !features.is_sorted() {
+                    self.report(
+                        2,
+                        "unsorted-features",
+                        line,
+                        format!("Features are not sorted, expected {features}"),
+                    );
+                }
+                for (key, value) in features.iter() {
+                    let values: Vec<&str> = value.split(',').collect();
+                    if (1..values.len()).any(|i| values[..i].contains(&values[i])) {
+                        self.report(
+                            2,
+                            "repeated-feature-value",
+                            line,
+                            format!("Feature {key} has repeated values {value}"),
+                        );
+                    }
+                }
+            }
+
+            if let TokenID::Range(_, _) = token.id {
+                if token.lemma.is_some()
+                    || token.upos.is_some()
+                    || token.xpos.is_some()
+                    || token.features.is_some()
+                    || token.deprel.is_some()
+                    || token.deps.is_some()
+                {
+                    self.report(
+                        2,
+                        "range-fields",
+                        line,
+                        "Multiword tokens may only have ID, FORM and MISC".into(),
+                    );
+                }
+                continue;
+            }
+
+            if let Some(deprel) = &token.deprel {
+                let universal = deprel.split(':').next().unwrap_or_default();
+                if !UNIVERSAL_DEPRELS.contains(&universal) {
+                    self.report(
+                        2,
+                        "unknown-deprel",
+                        line,
+                        format!("{universal} is not a universal relation"),
+                    );
+                }
+            }
+
+            if let (Some(head), Some(deprel), Some(deps)) = (token.head, &token.deprel, &token.deps)
+            {
+                // enhanced relations may add case information to the basic one
+                let universal = deprel.split(':').next();
+                let found = deps
+                    .iter()
+                    .any(|d| d.head == head && d.rel.split(':').next() == universal);
+                // words attached to an elided predicate depend on its empty node
+                // in the enhanced graph, and on a word promoted in its place in
+                // the basic tree, which is connected to the empty node
+                let elided = universal == Some("orphan")
+                    || deps.iter().any(|d| {
+                        matches!(d.head, TokenID::Empty(..))
+                            && (self.enhanced_edge(head, d.head)
+                                || self.enhanced_edge(d.head, head))
+                    });
+                if !found && !elided {
+                    self.report(
+                        2,
+                        "deps-missing-head",
+                        line,
+                        format!("DEPS do not contain HEAD:DEPREL {head}:{deprel}"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// The text must match the forms of the surface tokens.
+    fn text(&mut self) {
+        let Some(text) = self.sentence.meta.text() else {
+            return;
+        };
+
+        let mut reconstructed = String::new();
+        let mut covered_until = 0;
+        for token in &self.sentence.tokens {
+            match token.id {
+                TokenID::Range(_, end) => covered_until = end,
+                TokenID::Single(id) if id > covered_until => {}
+                _ => continue,
+            }
+            reconstructed.push_str(&token.form);
+            if token.misc.as_ref().map(|m| m.space_after()).unwrap_or(true) {
+                reconstructed.push(' ');
+            }
+        }
+
+        if text.trim_end() != reconstructed.trim_end() {
+            let line = self
+                .attribute_lines("text")
+                .next()
+                .map_or(self.line, |(line, _)| line);
+            self.report(
+                2,
+                "text-mismatch",
+                line,
+                format!(
+                    "text {:?} does not match the forms {:?}",
+                    text,
+                    reconstructed.trim_end()
+                ),
+            );
+        }
+    }
+
+    /// Universal constraints on the annotation.
+    fn content(&mut self) {
+        for (i, token) in self.sentence.tokens.iter().enumerate() {
+            let line = self.token_line(i);
+            let TokenID::Single(id) = token.id else {
+                continue;
+            };
+            let deprel = token.deprel.as_deref().unwrap_or_default();
+            let universal = deprel.split(':').next().unwrap_or_default();
+
+            if (universal == "punct") != (token.upos == Some(UPOS::PUNCT)) && token.upos.is_some() {
+                self.report(
+                    3,
+                    "rel-upos-punct",
+                    line,
+                    "DEPREL punct must be used with UPOS PUNCT and vice versa".into(),
+                );
+            }
+
+            if let Some(TokenID::Single(head)) = token.head {
+                if matches!(universal, "conj" | "fixed" | "flat" | "goeswith") && head > id {
+                    self.report(
+                        3,
+                        "right-to-left-relation",
+                        line,
+                        format!("Relation {universal} must go from left to right"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse_sentence;
+
+    use super::*;
+
+    fn rules(findings: &[Finding]) -> Vec<&'static str> {
+        findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_ids() {
+        let sentence = parse_sentence(
+            "1-5\tvámonos\t_\t_\t_\t_\t_\t_\t_\t_
+1\tvamos\tir\tVERB\t_\t_\t0\troot\t_\t_
+2\tnos\tnosotros\tPRON\t_\t_\t1\tobj\t_\t_
+1.1\tvamos\tir\tVERB\t_\t_\t_\t_\t_\t_
+4\ta\ta\tADP\t_\t_\t1\tobl\t_\t_
+",
+        )
+        .unwrap();
+
+        let findings = Validator::with_level(1).validate_sentence(&sentence, 1);
+
+        assert_eq!(
+            rules(&findings),
+            vec!["invalid-range", "misplaced-empty-node", "word-id-sequence"]
+        );
+        assert_eq!(findings[1].line, 4);
+    }
+
+    #[test]
+    fn test_heads() {
+        let sentence = parse_sentence(
+            "1\ta\ta\tX\t_\t_\t0\troot\t_\t_
+2\tb\tb\tX\t_\t_\t3\tdep\t_\t_
+3\tc\tc\tX\t_\t_\t2\tdep\t_\t_
+4\td\td\tX\t_\t_\t0\troot\t_\t_
+5\te\te\tX\t_\t_\t7\tdep\t_\t_
+",
+        )
+        .unwrap();
+
+        let findings = Validator::with_level(2).validate_sentence(&sentence, 1);
+
+        assert_eq!(
+            rules(&findings),
+            vec![
+                "missing-sent-id",
+                "missing-text",
+                "unknown-head",
+                "multiple-roots",
+                "head-cycle"
+            ]
+        );
+        assert_eq!(findings[3].line, 4);
+        assert_eq!(findings[4].message, "HEADs form a cycle: 2 -> 3");
+    }
+
+    #[test]
+    fn test_metadata_and_text() {
+        let first = parse_sentence(
+            "# newdoc
+# sent_id = s1
+# text = Don't go
+1-2\tDon't\t_\t_\t_\t_\t_\t_\t_\t_
+1\tDo\tdo\tAUX\t_\t_\t3\taux\t_\t_
+2\tn't\tnot\tPART\t_\t_\t3\tadvmod\t_\t_
+3\tgo\tgo\tVERB\t_\t_\t0\troot\t_\tSpaceAfter=No
+",
+        )
+        .unwrap();
+        let second = parse_sentence(
+            "# newpar
+# a comment
+# sent_id = s1
+# text = Go  home
+1\tGo\tgo\tVERB\t_\tMood=Imp|VerbForm=Fin|Aspect=Perf\t0\troot\t_\t_
+2\thome\thome\tADV\t_\t_\t1\tadvmod:lmod\t_\tSpaceAfter=No
+",
+        )
+        .unwrap();
+
+        let mut validator = Validator::new();
+
+        assert_eq!(validator.validate_sentence(&first, 1), vec![]);
+        let findings = validator.validate_sentence(&second, 9);
+        assert_eq!(
+            rules(&findings),
+            vec!["duplicate-sent-id", "unsorted-features", "text-mismatch"]
+        );
+        assert_eq!(findings[0].sent_id.as_deref(), Some("s1"));
+        assert_eq!(findings[0].line, 11);
+        assert_eq!(findings[0].message, "sent_id s1 was already used in line 2");
+        assert_eq!(findings[1].line, 13);
+        assert_eq!(findings[2].line, 12);
+    }
+
+    #[test]
+    fn test_enhanced_deps() {
+        let sentence = parse_sentence(
+            "# sent_id = 1
+# text = Mary won gold and Peter bronze of tin x y
+1\tMary\tMary\tPROPN\t_\t_\t2\tnsubj\t2:nsubj\t_
+2\twon\twin\tVERB\t_\t_\t0\troot\t0:root\t_
+3\tgold\tgold\tNOUN\t_\t_\t2\tobj\t2:obj\t_
+4\tand\tand\tCCONJ\t_\t_\t5\tcc\t5.1:cc\t_
+5\tPeter\tPeter\tPROPN\t_\t_\t2\tconj\t5.1:nsubj\t_
+5.1\twon\twin\tVERB\t_\t_\t_\t_\t2:conj:and\t_
+6\tbronze\tbronze\tNOUN\t_\t_\t5\torphan\t5.1:obj\t_
+7\tof\tof\tADP\t_\t_\t8\tcase\t8:case\t_
+8\ttin\ttin\tNOUN\t_\t_\t6\tnmod\t6:nmod:of\t_
+9\tx\tx\tX\t_\t_\t3\tdep\t5.1:dep\t_
+10\ty\ty\tX\t_\t_\t2\tdep\t2:obj\t_
+",
+        )
+        .unwrap();
+
+        let findings = Validator::with_level(2).validate_sentence(&sentence, 1);
+
+        assert_eq!(
+            rules(&findings),
+            vec!["deps-missing-head", "deps-missing-head"]
+        );
+        assert_eq!(findings[0].line, 12);
+        assert_eq!(findings[1].line, 13);
+    }
+
+    #[test]
+    fn test_validate_doc() {
+        let input = "# sent_id = 1
+# text = a
+1\ta\ta\tX\t_\t_\t0\troot\t_\t_
+
+# sent_id = 2
+# text = b
+1\tb\tb\tX\t_\t_\t0
+2\tc\tc\tX\t_\t_\t1\tdep\t_\t_
+3\td\td\tX\t_\tFoo\t1\tdep\t_\t_
+";
+
+        let findings = Validator::new().validate_doc(Doc::new(input.as_bytes()));
+
+        assert_eq!(rules(&findings), vec!["parse-error", "parse-error"]);
+        assert_eq!(findings[0].line, 7);
+        assert_eq!(findings[0].char_column, Some(14));
+        assert_eq!(findings[1].line, 9);
+        assert_eq!(findings[1].char_column, Some(11));
+        assert_eq!(findings[1].sent_id.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_validate_doc_interleaved_comment() {
+        let input = "# sent_id = 1
+1\ta\ta\tX\t_\t_\t1\tdep\t_\t_
+# text = a b
+2\tb\tb\tX\t_\t_\t0\troot\t_\t_
+";
+
+        let findings = Validator::with_level(2).validate_doc(Doc::new(input.as_bytes()));
+
+        assert_eq!(rules(&findings), vec!["head-self-loop"]);
+        assert_eq!(findings[0].line, 2);
+    }
+}
@@ -0,0 +1,170 @@
+use std::io::{self, Write};
+
+use crate::{parsers::DeclaredColumns, Sentence};
+
+/// A `Writer` serializes [Sentence]s in CoNLL-U format to any type that
+/// implements [Write]. Each sentence is followed by a blank line, as
+/// required by the specification.
+///
+/// When a sentence declares `global.columns`, that sentence and all following
+/// ones are written with the declared CoNLL-U Plus columns.
+///
+/// ```rust
+/// use rs_conllu::{parse_sentence, Writer};
+///
+/// let input = "# sent_id = 1
+/// 1\tHello\thello\tINTJ\t_\t_\t0\troot\t_\t_
+/// ";
+///
+/// let sentence = parse_sentence(input).unwrap();
+///
+/// let mut writer = Writer::new(vec![]);
+/// writer.write_sentence(&sentence).unwrap();
+///
+/// let output = String::from_utf8(writer.into_inner()).unwrap();
+/// assert_eq!(output, format!("{input}\n"));
+/// ```
+pub struct Writer<W: Write> {
+    writer: W,
+    columns: DeclaredColumns,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(writer: W) -> Self {
+        Writer {
+            writer,
+            columns: DeclaredColumns::default(),
+        }
+    }
+
+    /// Write a single sentence, including its comment lines and
+    /// the terminating blank line.
+    pub fn write_sentence(&mut self, sentence: &Sentence) -> io::Result<()> {
+        self.columns.update(&sentence.meta);
+
+        write!(
+            self.writer,
+            "{}",
+            sentence.display_with(&self.columns.columns())
+        )?;
+        writeln!(self.writer)
+    }
+
+    /// Write all sentences of an iterator, e.g. a whole document.
+    pub fn write_all<'a, I>(&mut self, sentences: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'a Sentence>,
+    {
+        for sentence in sentences {
+            self.write_sentence(sentence)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Consume the `Writer` and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse_sentence;
+
+    use super::*;
+
+    #[test]
+    fn test_sentence_roundtrip() {
+        let input = "# sent_id = 2
+# text = Ich gehe zum Bahnhof.
+1	Ich	ich	PRON	PPER	Case=Nom|Number=Sing|Person=1	2	nsubj	_	_
+2	gehe	gehen	VERB	VVFIN	Mood=Ind|Number=Sing|Person=1|Tense=Pres	0	root	0:root	_
+3-4	zum	_	_	_	_	_	_	_	_
+3	zu	zu	ADP	APPR	_	5	case	5:case	_
+4	dem	der	DET	ART	Case=Dat|Definite=Def	5	det	5:det	_
+4.1	geht	gehen	VERB	_	_	_	_	2:conj	_
+5	Bahnhof	Bahnhof	NOUN	NN	Case=Dat|Gender=Masc|Number=Sing	2	obl	2:obl|4.1:obl	SpaceAfter=No
+6	.	.	PUNCT	$.	_	2	punct	2:punct	_
+";
+
+        let sentence = parse_sentence(input).unwrap();
+        assert_eq!(sentence.to_string(), input);
+        assert_eq!(parse_sentence(&sentence.to_string()).unwrap(), sentence);
+    }
+
+    #[test]
+    fn test_unsorted_features_roundtrip() {
+        let input = "1\twhich\twhich\tPRON\t_\tPronType=Rel,Int|Case=Nom\t0\troot\t_\t_\n";
+
+        let sentence = parse_sentence(input).unwrap();
+        let output = sentence.to_string();
+        assert_eq!(
+            output,
+            "1\twhich\twhich\tPRON\t_\tCase=Nom|PronType=Int,Rel\t0\troot\t_\t_\n"
+        );
+        assert_eq!(parse_sentence(&output).unwrap(), sentence);
+    }
+
+    #[test]
+    fn test_literal_underscore_roundtrip() {
+        let input = "1-2\t_\t_\t_\t_\t_\t_\t_\t_\t_
+1\t_\t_\tPUNCT\t_\t_\t3\tpunct\t_\t_
+2\t_\tbe\tAUX\t_\t_\t3\tcop\t_\t_
+3\tfine\tfine\tADJ\t_\t_\t0\troot\t_\t_
+";
+
+        let sentence = parse_sentence(input).unwrap();
+        assert_eq!(sentence.tokens[0].lemma, None);
+        assert!(sentence.tokens[1].is_literal_underscore());
+        assert!(!sentence.tokens[2].is_literal_underscore());
+
+        let mut writer = Writer::new(vec![]);
+        writer.write_sentence(&sentence).unwrap();
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(output, format!("{input}\n"));
+        assert_eq!(parse_sentence(&output).unwrap(), sentence);
+    }
+
+    #[test]
+    fn test_writer_separates_sentences() {
+        let first = parse_sentence("1\ta\t_\t_\t_\t_\t_\t_\t_\t_\n").unwrap();
+        let second = parse_sentence("1\tb\t_\t_\t_\t_\t_\t_\t_\t_\n").unwrap();
+
+        let mut writer = Writer::new(vec![]);
+        writer.write_all([&first, &second]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            "1\ta\t_\t_\t_\t_\t_\t_\t_\t_\n\n1\tb\t_\t_\t_\t_\t_\t_\t_\t_\n\n"
+        );
+    }
+
+    #[test]
+    fn test_conllu_plus_roundtrip() {
+        let input = "# global.columns = ID FORM PARSEME:MWE
+# sent_id = 1
+1\tkicked\t1:VID
+2\tthe\t1
+3\tbucket\t1
+
+# sent_id = 2
+1\tHello\t*
+
+";
+
+        let sentences = crate::parsers::Doc::new(input.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let mut writer = Writer::new(vec![]);
+        writer.write_all(&sentences).unwrap();
+
+        assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), input);
+    }
+}
@@ -1,6 +1,8 @@
-use std::{collections::HashMap, fs::File};
+use std::fs::File;
 
-use rs_conllu::{parse_file, token::Dep, token::Token, token::TokenID, UPOS};
+use rs_conllu::{
+    parse_file, token::Dep, token::Features, token::Token, token::TokenID, Writer, UPOS,
+};
 
 #[test]
 fn test_file_parse() {
@@ -20,7 +22,7 @@ fn test_file_parse() {
             lemma: Some("they".to_string()),
             upos: Some(UPOS::PRON),
             xpos: Some("PRP".to_string()),
-            features: Some(HashMap::from([
+            features: Some(Features::from([
                 ("Case".to_string(), "Nom".to_string()),
                 ("Number".to_string(), "Plur".to_string())
             ])),
@@ -36,7 +38,23 @@ fn test_file_parse() {
                     rel: "nsubj".to_string()
                 }
             ]),
-            misc: None
+            misc: None,
+            extra_columns: vec![],
         }
     )
 }
+
+#[test]
+fn test_file_roundtrip() {
+    let input = std::fs::read_to_string("./tests/example.conllu").unwrap();
+
+    let sentences = parse_file(File::open("./tests/example.conllu").unwrap())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let mut writer = Writer::new(vec![]);
+    writer.write_all(&sentences).unwrap();
+    let output = String::from_utf8(writer.into_inner()).unwrap();
+
+    assert_eq!(output, format!("{input}\n"));
+}